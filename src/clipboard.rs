@@ -0,0 +1,74 @@
+//! System clipboard access via OSC 52.
+//!
+//! OSC 52 asks the terminal emulator itself to set the clipboard, so it
+//! works over SSH and without any X11/Wayland client libraries. Terminals
+//! that don't understand the sequence get the text written to a file instead.
+
+use base64::Engine;
+use std::env;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+
+/// Largest payload sent through OSC 52. Several terminals (and tmux)
+/// silently drop sequences above roughly 100 KB.
+const MAX_OSC52_BYTES: usize = 100_000;
+
+/// File name used when the terminal can't take the text.
+const FALLBACK_FILE: &str = "lazydb-clipboard.txt";
+
+/// Where copied text ended up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyTarget {
+    /// Sent to the terminal clipboard via OSC 52.
+    Terminal,
+    /// Written to a fallback file.
+    File(PathBuf),
+}
+
+/// Copy text to the system clipboard, falling back to a file when OSC 52
+/// is unavailable or the payload is too large.
+pub fn copy(text: &str) -> std::io::Result<CopyTarget> {
+    if text.len() <= MAX_OSC52_BYTES && osc52_supported() {
+        write_osc52(text)?;
+        return Ok(CopyTarget::Terminal);
+    }
+    let path = env::temp_dir().join(FALLBACK_FILE);
+    std::fs::write(&path, text)?;
+    Ok(CopyTarget::File(path))
+}
+
+/// Best-effort detection of OSC 52 support.
+///
+/// `LAZYDB_CLIPBOARD=file` forces the fallback and `LAZYDB_CLIPBOARD=osc52`
+/// forces the escape sequence, for terminals the heuristic gets wrong.
+fn osc52_supported() -> bool {
+    match env::var("LAZYDB_CLIPBOARD").as_deref() {
+        Ok("file") => return false,
+        Ok("osc52") => return true,
+        _ => {}
+    }
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    // The Linux console and dumb terminals ignore OSC sequences entirely.
+    env::var("TERM").is_ok_and(|term| !matches!(term.as_str(), "" | "dumb" | "linux"))
+}
+
+fn write_osc52(text: &str) -> std::io::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let sequence = format!("\x1b]52;c;{encoded}\x07");
+
+    let mut stdout = std::io::stdout().lock();
+    if env::var_os("TMUX").is_some() {
+        // tmux only forwards OSC 52 to the outer terminal when wrapped in a
+        // DCS passthrough with every ESC doubled.
+        write!(
+            stdout,
+            "\x1bPtmux;{}\x1b\\",
+            sequence.replace('\x1b', "\x1b\x1b")
+        )?;
+    } else {
+        stdout.write_all(sequence.as_bytes())?;
+    }
+    stdout.flush()
+}
@@ -0,0 +1,299 @@
+//! `CREATE` statements for schema objects.
+//!
+//! MySQL hands these out directly via `SHOW CREATE`. Postgres has no
+//! equivalent for tables, so their DDL is rebuilt from the catalogs; views,
+//! indexes and functions use the matching `pg_get_*def` function.
+
+use crate::app::{DbType, quote_ident, quote_qualified};
+use crate::event::{DbIndex, DbRoutine};
+use sqlx::{AnyPool, Row};
+
+/// Object whose DDL is requested.
+#[derive(Debug, Clone)]
+pub enum DdlTarget {
+    Table {
+        schema: String,
+        table: String,
+    },
+    View {
+        schema: String,
+        view: String,
+    },
+    Index {
+        schema: String,
+        table: String,
+        index: DbIndex,
+    },
+    Routine {
+        schema: String,
+        routine: DbRoutine,
+    },
+}
+
+impl DdlTarget {
+    /// `schema.name` of the object, used as the view title.
+    pub fn display_name(&self) -> String {
+        match self {
+            Self::Table { schema, table } => format!("{schema}.{table}"),
+            Self::View { schema, view } => format!("{schema}.{view}"),
+            Self::Index { schema, index, .. } => format!("{schema}.{}", index.name),
+            Self::Routine { schema, routine } => {
+                format!("{schema}.{}({})", routine.name, routine.arguments)
+            }
+        }
+    }
+}
+
+/// Fetch or rebuild the `CREATE` statement for `target`.
+pub async fn fetch_ddl(
+    pool: &AnyPool,
+    db_type: DbType,
+    target: &DdlTarget,
+) -> Result<String, String> {
+    match target {
+        DdlTarget::Table { schema, table } => match db_type {
+            DbType::Postgres => postgres_table_ddl(pool, schema, table).await,
+            DbType::MySQL => mysql_show_create(pool, "TABLE", schema, table).await,
+        },
+        DdlTarget::View { schema, view } => match db_type {
+            DbType::Postgres => postgres_view_ddl(pool, schema, view).await,
+            DbType::MySQL => mysql_show_create(pool, "VIEW", schema, view).await,
+        },
+        DdlTarget::Index {
+            schema,
+            table,
+            index,
+        } => match db_type {
+            DbType::Postgres => {
+                let def: String = sqlx::query_scalar(&format!(
+                    "SELECT pg_get_indexdef({}::regclass)",
+                    regclass_literal(schema, &index.name)
+                ))
+                .fetch_one(pool)
+                .await
+                .map_err(|e| format!("Failed to load index definition: {e}"))?;
+                Ok(format!("{def};"))
+            }
+            // MySQL has no standalone index DDL; rebuild it from the catalog entry
+            DbType::MySQL => Ok(mysql_index_ddl(schema, table, index)),
+        },
+        DdlTarget::Routine { schema, routine } => {
+            fetch_routine_definition(pool, db_type, schema, routine).await
+        }
+    }
+}
+
+/// `'"schema"."name"'` for casting to `regclass`.
+fn regclass_literal(schema: &str, name: &str) -> String {
+    let qualified = format!(
+        "{}.{}",
+        quote_ident(&schema.replace('"', "\"\""), DbType::Postgres),
+        quote_ident(&name.replace('"', "\"\""), DbType::Postgres)
+    );
+    format!("'{}'", qualified.replace('\'', "''"))
+}
+
+async fn mysql_show_create(
+    pool: &AnyPool,
+    kind: &str,
+    schema: &str,
+    name: &str,
+) -> Result<String, String> {
+    let row = sqlx::query(&format!(
+        "SHOW CREATE {kind} {}",
+        quote_qualified(&format!("{schema}.{name}"), DbType::MySQL)
+    ))
+    .fetch_one(pool)
+    .await
+    .map_err(|e| format!("Failed to load definition: {e}"))?;
+    // Column 0 is the object name, column 1 the statement
+    let ddl: String = row
+        .try_get(1)
+        .map_err(|e| format!("Failed to read definition: {e}"))?;
+    Ok(format!("{ddl};"))
+}
+
+fn mysql_index_ddl(schema: &str, table: &str, index: &DbIndex) -> String {
+    let target = quote_qualified(&format!("{schema}.{table}"), DbType::MySQL);
+    let columns = index
+        .columns
+        .iter()
+        .map(|c| quote_ident(c, DbType::MySQL))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if index.is_primary {
+        return format!("ALTER TABLE {target} ADD PRIMARY KEY ({columns});");
+    }
+    let kind = match index.method.as_str() {
+        "fulltext" => "FULLTEXT ",
+        "spatial" => "SPATIAL ",
+        _ if index.is_unique => "UNIQUE ",
+        _ => "",
+    };
+    let using = match index.method.as_str() {
+        "btree" | "hash" => format!(" USING {}", index.method.to_uppercase()),
+        _ => String::new(),
+    };
+    format!(
+        "CREATE {kind}INDEX {} ON {target} ({columns}){using};",
+        quote_ident(&index.name, DbType::MySQL)
+    )
+}
+
+async fn postgres_view_ddl(pool: &AnyPool, schema: &str, view: &str) -> Result<String, String> {
+    let regclass = regclass_literal(schema, view);
+    let row = sqlx::query(&format!(
+        "SELECT c.relkind::text, pg_get_viewdef(c.oid, true) \
+         FROM pg_class c WHERE c.oid = {regclass}::regclass"
+    ))
+    .fetch_one(pool)
+    .await
+    .map_err(|e| format!("Failed to load view definition: {e}"))?;
+    let relkind: String = row.try_get(0).unwrap_or_default();
+    let body: String = row.try_get(1).unwrap_or_default();
+    let create = if relkind == "m" {
+        "CREATE MATERIALIZED VIEW"
+    } else {
+        "CREATE OR REPLACE VIEW"
+    };
+    let name = quote_qualified(&format!("{schema}.{view}"), DbType::Postgres);
+    // pg_get_viewdef already ends the query with a semicolon
+    Ok(format!("{create} {name} AS\n{}", body.trim_end()))
+}
+
+/// Rebuild `CREATE TABLE` from the catalogs: columns with defaults and
+/// identity, table constraints, then any indexes not backing a constraint.
+async fn postgres_table_ddl(pool: &AnyPool, schema: &str, table: &str) -> Result<String, String> {
+    let regclass = regclass_literal(schema, table);
+
+    let columns = sqlx::query(&format!(
+        r#"SELECT a.attname::text, format_type(a.atttypid, a.atttypmod),
+                  CASE WHEN a.attnotnull THEN 'YES' ELSE 'NO' END,
+                  COALESCE(pg_get_expr(d.adbin, d.adrelid), ''),
+                  a.attidentity::text, a.attgenerated::text,
+                  COALESCE(co.collname::text, '')
+           FROM pg_attribute a
+           LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+           LEFT JOIN pg_collation co ON co.oid = a.attcollation
+               AND a.attcollation <> (SELECT t.typcollation FROM pg_type t WHERE t.oid = a.atttypid)
+           WHERE a.attrelid = {regclass}::regclass AND a.attnum > 0 AND NOT a.attisdropped
+           ORDER BY a.attnum"#
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load columns: {e}"))?;
+
+    let constraints = sqlx::query(&format!(
+        r#"SELECT conname::text, pg_get_constraintdef(oid, true)
+           FROM pg_constraint
+           WHERE conrelid = {regclass}::regclass
+           ORDER BY CASE contype WHEN 'p' THEN 0 WHEN 'u' THEN 1 WHEN 'f' THEN 2 ELSE 3 END, conname"#
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load constraints: {e}"))?;
+
+    let indexes: Vec<String> = sqlx::query_scalar(&format!(
+        r#"SELECT pg_get_indexdef(i.indexrelid)
+           FROM pg_index i
+           JOIN pg_class c ON c.oid = i.indexrelid
+           WHERE i.indrelid = {regclass}::regclass
+             AND NOT EXISTS (SELECT 1 FROM pg_constraint k WHERE k.conindid = i.indexrelid)
+           ORDER BY c.relname"#
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load indexes: {e}"))?;
+
+    let mut lines: Vec<String> = columns
+        .iter()
+        .map(|r| {
+            let get = |i: usize| r.try_get::<String, _>(i).unwrap_or_default();
+            let mut line = format!("    {} {}", quote_ident(&get(0), DbType::Postgres), get(1));
+            let collation = get(6);
+            if !collation.is_empty() {
+                line.push_str(&format!(
+                    " COLLATE {}",
+                    quote_ident(&collation, DbType::Postgres)
+                ));
+            }
+            let default = get(3);
+            match (get(4).as_str(), get(5).as_str()) {
+                ("a", _) => line.push_str(" GENERATED ALWAYS AS IDENTITY"),
+                ("d", _) => line.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+                (_, "s") => line.push_str(&format!(" GENERATED ALWAYS AS ({default}) STORED")),
+                _ if !default.is_empty() => line.push_str(&format!(" DEFAULT {default}")),
+                _ => {}
+            }
+            if get(2) == "YES" {
+                line.push_str(" NOT NULL");
+            }
+            line
+        })
+        .collect();
+    lines.extend(constraints.iter().map(|r| {
+        let name: String = r.try_get(0).unwrap_or_default();
+        let def: String = r.try_get(1).unwrap_or_default();
+        format!(
+            "    CONSTRAINT {} {def}",
+            quote_ident(&name, DbType::Postgres)
+        )
+    }));
+
+    let mut ddl = format!(
+        "CREATE TABLE {} (\n{}\n);",
+        quote_qualified(&format!("{schema}.{table}"), DbType::Postgres),
+        lines.join(",\n")
+    );
+    for index in indexes {
+        ddl.push_str(&format!("\n\n{index};"));
+    }
+    Ok(ddl)
+}
+
+/// Full `CREATE` statement for a stored function or procedure.
+pub async fn fetch_routine_definition(
+    pool: &AnyPool,
+    db_type: DbType,
+    schema: &str,
+    routine: &DbRoutine,
+) -> Result<String, String> {
+    match db_type {
+        DbType::Postgres => {
+            if !routine.specific_name.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("Invalid function oid: {}", routine.specific_name));
+            }
+            sqlx::query_scalar(&format!(
+                "SELECT pg_get_functiondef({}::oid)",
+                routine.specific_name
+            ))
+            .fetch_one(pool)
+            .await
+            .map_err(|e| format!("Failed to load definition: {e}"))
+        }
+        DbType::MySQL => {
+            let body: Option<String> = sqlx::query_scalar(&format!(
+                "SELECT routine_definition FROM information_schema.routines \
+                 WHERE routine_schema = '{}' AND specific_name = '{}'",
+                schema.replace('\'', "''"),
+                routine.specific_name.replace('\'', "''")
+            ))
+            .fetch_one(pool)
+            .await
+            .map_err(|e| format!("Failed to load definition: {e}"))?;
+            let returns = if routine.result.is_empty() {
+                String::new()
+            } else {
+                format!(" RETURNS {}", routine.result)
+            };
+            Ok(format!(
+                "CREATE {} {}({}){}\n{}",
+                routine.kind.keyword(),
+                quote_qualified(&format!("{schema}.{}", routine.name), db_type),
+                routine.arguments,
+                returns,
+                body.unwrap_or_default()
+            ))
+        }
+    }
+}
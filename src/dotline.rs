@@ -1,7 +1,9 @@
 //! ASCII bar-graph widget (gitui/btop style).
 //!
 //! Renders a fixed-height bar graph where each column is a solid vertical
-//! bar using a single consistent glyph.
+//! bar, either with one `.`/`:` glyph per sample or in braille at two
+//! samples and four levels per cell. The newest sample sits at the right
+//! edge; samples marked as gaps are left blank.
 
 use ratatui::{
     buffer::Buffer,
@@ -16,29 +18,85 @@ use std::collections::VecDeque;
 const DOT_SINGLE: char = '.';
 const DOT_DOUBLE: char = ':';
 
+/// Braille dot bits for the left and right column of a cell, top to bottom.
+const BRAILLE_LEFT: [u32; 4] = [0x01, 0x02, 0x04, 0x40];
+const BRAILLE_RIGHT: [u32; 4] = [0x08, 0x10, 0x20, 0x80];
+
 /// Default graph height in rows.
 pub const DEFAULT_HEIGHT: u16 = 4;
 
 /// Dim color for zero/empty values.
 const ZERO_COLOR: Color = Color::Rgb(50, 50, 50);
+/// Background of columns on a time-axis marker.
+const MARKER_BG: Color = Color::Rgb(35, 35, 35);
+/// Background of the column under the cursor.
+const CURSOR_BG: Color = Color::Rgb(70, 70, 70);
+/// Color of the y-axis labels.
+const AXIS_COLOR: Color = Color::Rgb(110, 110, 110);
+
+/// An overlaid series: its samples, which of them are gaps, and its color.
+type Overlay<'a> = (&'a VecDeque<u64>, Option<&'a VecDeque<bool>>, Color);
+
+/// How samples are drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphMode {
+    /// One sample per cell and one level per row.
+    #[default]
+    Dots,
+    /// Two samples per cell and four levels per row.
+    Braille,
+}
+
+impl GraphMode {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Dots => Self::Braille,
+            Self::Braille => Self::Dots,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dots => "dots",
+            Self::Braille => "braille",
+        }
+    }
+
+    fn samples_per_cell(self) -> usize {
+        match self {
+            Self::Dots => 1,
+            Self::Braille => 2,
+        }
+    }
+}
 
 /// ASCII bar-graph widget with solid vertical bars.
 ///
 /// Renders a fixed-height graph where each data point shows a solid
-/// vertical bar from the peak position down to the baseline, using
-/// a single consistent glyph throughout.
+/// vertical bar from the peak position down to the baseline.
 pub struct AsciiDotGraph<'a, F>
 where
     F: Fn(u64, u64) -> Color,
 {
     /// Data points to render.
     data: &'a VecDeque<u64>,
+    /// Which of `data` are gaps, index for index.
+    gaps: Option<&'a VecDeque<bool>>,
     /// Maximum value for scaling.
     max: u64,
     /// Height in rows.
     height: u16,
     /// Function to compute color for a value given (value, max).
     color_fn: F,
+    mode: GraphMode,
+    /// Label the top and bottom rows with the scale's max and min.
+    y_labels: bool,
+    /// Shade every n-th sample, counting back from the newest.
+    marker_every: Option<usize>,
+    /// Highlight the sample this many steps back from the newest.
+    cursor: Option<usize>,
+    /// Further series drawn over `data`, each in a fixed color.
+    overlays: Vec<Overlay<'a>>,
 }
 
 impl<'a, F> AsciiDotGraph<'a, F>
@@ -49,18 +107,90 @@ where
     pub fn new(data: &'a VecDeque<u64>, max: u64, color_fn: F) -> Self {
         Self {
             data,
+            gaps: None,
             max: max.max(1),
             height: DEFAULT_HEIGHT,
             color_fn,
+            mode: GraphMode::default(),
+            y_labels: false,
+            marker_every: None,
+            cursor: None,
+            overlays: Vec::new(),
         }
     }
 
+    /// Leave the samples of `data` flagged in `gaps` blank.
+    pub fn gaps(mut self, gaps: Option<&'a VecDeque<bool>>) -> Self {
+        self.gaps = gaps;
+        self
+    }
+
+    /// Draw another series on the same scale. Where bars overlap, the
+    /// shorter one shows in front.
+    pub fn overlay(
+        mut self,
+        data: &'a VecDeque<u64>,
+        gaps: Option<&'a VecDeque<bool>>,
+        color: Color,
+    ) -> Self {
+        self.overlays.push((data, gaps, color));
+        self
+    }
+
     /// Set the height of the graph.
     pub fn height(mut self, height: u16) -> Self {
         self.height = height.max(1);
         self
     }
 
+    pub fn mode(mut self, mode: GraphMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Show min/max labels in a left gutter; needs at least two rows.
+    pub fn y_labels(mut self, show: bool) -> Self {
+        self.y_labels = show;
+        self
+    }
+
+    /// Shade the columns of every `every`-th sample back from the newest.
+    pub fn time_marker(mut self, every: usize) -> Self {
+        self.marker_every = (every > 0).then_some(every);
+        self
+    }
+
+    pub fn cursor(mut self, samples_back: Option<usize>) -> Self {
+        self.cursor = samples_back;
+        self
+    }
+
+    /// Values of every series for the slots of one cell, `None` on a gap,
+    /// tallest series first, each with its color.
+    fn cell_layers(&self, first_slot: usize, slots: usize) -> Vec<(Vec<Option<u64>>, Color)> {
+        let per_cell = self.mode.samples_per_cell();
+        let mut layers: Vec<(Vec<Option<u64>>, Color)> =
+            std::iter::once((self.data, self.gaps, None))
+                .chain(
+                    self.overlays
+                        .iter()
+                        .map(|(data, gaps, color)| (*data, *gaps, Some(*color))),
+                )
+                .map(|(data, gaps, color)| {
+                    let values: Vec<Option<u64>> = (first_slot..first_slot + per_cell)
+                        .map(|slot| sample(data, gaps, slot, slots))
+                        .collect();
+                    let peak = values.iter().flatten().copied().max().unwrap_or(0);
+                    (
+                        values,
+                        color.unwrap_or_else(|| (self.color_fn)(peak, self.max)),
+                    )
+                })
+                .collect();
+        layers.sort_by_key(|(values, _)| std::cmp::Reverse(values.iter().flatten().max().copied()));
+        layers
+    }
+
     /// Build the grid of lines for rendering.
     fn build_grid(&self, width: usize) -> Vec<Line<'static>> {
         let height = self.height as usize;
@@ -68,55 +198,208 @@ where
             return vec![];
         }
 
-        let data_len = self.data.len();
-        let start_idx = data_len.saturating_sub(width);
+        let max_label = axis_label(self.max);
+        let gutter = if self.y_labels && height >= 2 {
+            max_label.len() + 1
+        } else {
+            0
+        };
+        let plot_width = width.saturating_sub(gutter);
+        let per_cell = self.mode.samples_per_cell();
+        let slots = plot_width * per_cell;
 
-        // Initialize grid with spaces
         let mut grid: Vec<Vec<Span<'static>>> = (0..height)
-            .map(|_| vec![Span::raw(" ".to_string()); width])
+            .map(|row| {
+                if gutter == 0 {
+                    return Vec::with_capacity(plot_width);
+                }
+                let label = match row {
+                    0 => max_label.as_str(),
+                    _ if row == height - 1 => "0",
+                    _ => "",
+                };
+                vec![Span::styled(
+                    format!("{label:>w$} ", w = gutter - 1),
+                    Style::default().fg(AXIS_COLOR),
+                )]
+            })
             .collect();
 
-        // Fill in bars for each data point
-        for col in 0..width {
-            let data_idx = start_idx + col;
-            let value = if data_idx < data_len {
-                self.data.get(data_idx).copied().unwrap_or(0)
+        for col in 0..plot_width {
+            let first = col * per_cell;
+            let layers = self.cell_layers(first, slots);
+
+            // Steps back from the newest sample for each slot in this cell
+            let ages = (first..first + per_cell).map(|slot| slots - 1 - slot);
+            let (mut marked, mut hovered) = (false, false);
+            for age in ages {
+                marked |= self
+                    .marker_every
+                    .is_some_and(|every| age > 0 && age % every == 0);
+                hovered |= self.cursor == Some(age);
+            }
+            let bg = if hovered {
+                Some(CURSOR_BG)
+            } else if marked {
+                Some(MARKER_BG)
             } else {
-                0
+                None
             };
 
-            if value == 0 {
-                // For zero values, show a dim dot at the bottom only
-                let row = height - 1;
-                grid[row][col] =
-                    Span::styled(DOT_SINGLE.to_string(), Style::default().fg(ZERO_COLOR));
-            } else {
-                // Calculate top_row: higher values = smaller row number (closer to top)
-                // top_row = H - 1 - (v * (H - 1) / max)
-                let h_minus_1 = (height - 1) as f64;
-                let normalized = (value as f64 / self.max as f64).min(1.0);
-                let top_row = (h_minus_1 - (normalized * h_minus_1)).round() as usize;
-                let top_row = top_row.min(height - 1);
+            let column = match self.mode {
+                GraphMode::Dots => self.dots_column(&layers, height),
+                GraphMode::Braille => self.braille_column(&layers, height),
+            };
+            for (row, (glyph, color)) in grid.iter_mut().zip(column) {
+                let mut style = Style::default();
+                if let Some(color) = color {
+                    style = style.fg(color);
+                }
+                if let Some(bg) = bg {
+                    style = style.bg(bg);
+                }
+                row.push(Span::styled(glyph.to_string(), style));
+            }
+        }
 
-                // Get color for this value
-                let color = (self.color_fn)(value, self.max);
+        // Convert grid to lines
+        grid.into_iter().map(Line::from).collect()
+    }
 
-                // Use '.' for peak (top), ':' for fill below
-                let peak_span = Span::styled(DOT_SINGLE.to_string(), Style::default().fg(color));
-                let fill_span = Span::styled(DOT_DOUBLE.to_string(), Style::default().fg(color));
+    /// One cell per row for a single sample per series, top to bottom.
+    fn dots_column(
+        &self,
+        layers: &[(Vec<Option<u64>>, Color)],
+        height: usize,
+    ) -> Vec<(char, Option<Color>)> {
+        let mut column = vec![(' ', None); height];
+        if layers.iter().all(|(values, _)| values[0].is_none()) {
+            return column;
+        }
+        if layers.iter().all(|(values, _)| values[0].unwrap_or(0) == 0) {
+            // For zero values, show a dim dot at the bottom only
+            column[height - 1] = (DOT_SINGLE, Some(ZERO_COLOR));
+            return column;
+        }
 
-                // Draw peak at top_row
-                grid[top_row][col] = peak_span;
+        for (values, color) in layers {
+            let value = values[0].unwrap_or(0);
+            if value == 0 {
+                continue;
+            }
+            // Calculate top_row: higher values = smaller row number (closer to top)
+            // top_row = H - 1 - (v * (H - 1) / max)
+            let h_minus_1 = (height - 1) as f64;
+            let normalized = (value as f64 / self.max as f64).min(1.0);
+            let top_row = (h_minus_1 - (normalized * h_minus_1)).round() as usize;
+            let top_row = top_row.min(height - 1);
 
-                // Fill from top_row+1 down to baseline with ':'
-                for row in grid.iter_mut().take(height).skip(top_row + 1) {
-                    row[col] = fill_span.clone();
-                }
+            // Use '.' for peak (top), ':' for fill below
+            column[top_row] = (DOT_SINGLE, Some(*color));
+            for cell in column.iter_mut().skip(top_row + 1) {
+                *cell = (DOT_DOUBLE, Some(*color));
             }
         }
+        column
+    }
 
-        // Convert grid to lines
-        grid.into_iter().map(Line::from).collect()
+    /// One braille cell per row for two samples per series, top to bottom.
+    fn braille_column(
+        &self,
+        layers: &[(Vec<Option<u64>>, Color)],
+        height: usize,
+    ) -> Vec<(char, Option<Color>)> {
+        let levels = height * 4;
+        // Any non-zero value lights at least the bottom dot
+        let level_of = |value: u64| {
+            if value == 0 {
+                0
+            } else {
+                let normalized = (value as f64 / self.max as f64).min(1.0);
+                ((normalized * levels as f64).round() as usize).max(1)
+            }
+        };
+        let idle = layers
+            .iter()
+            .all(|(values, _)| values.iter().all(|v| v.unwrap_or(0) == 0));
+        // Baseline dots only under samples that aren't gaps in every series
+        let baseline = [BRAILLE_LEFT[3], BRAILLE_RIGHT[3]]
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| layers.iter().any(|(values, _)| values[*i].is_some()))
+            .fold(0, |bits, (_, bit)| bits | bit);
+
+        (0..height)
+            .map(|row| {
+                if idle && row == height - 1 && baseline != 0 {
+                    // Baseline for an idle stretch
+                    return (braille(baseline), Some(ZERO_COLOR));
+                }
+                let mut bits = 0;
+                let mut color = None;
+                for (values, layer_color) in layers {
+                    let filled: Vec<usize> =
+                        values.iter().map(|v| level_of(v.unwrap_or(0))).collect();
+                    let mut layer_bits = 0;
+                    for (sub_row, (left, right)) in
+                        BRAILLE_LEFT.iter().zip(BRAILLE_RIGHT).enumerate()
+                    {
+                        // 1-based level of this dot, counted from the bottom
+                        let level = (height - 1 - row) * 4 + (4 - sub_row);
+                        for (bit, filled) in [*left, right].into_iter().zip(&filled) {
+                            if level <= *filled {
+                                layer_bits |= bit;
+                            }
+                        }
+                    }
+                    // Layers run tallest first, so the shortest one in
+                    // this cell decides its color
+                    if layer_bits != 0 {
+                        bits |= layer_bits;
+                        color = Some(*layer_color);
+                    }
+                }
+                if bits == 0 {
+                    (' ', None)
+                } else {
+                    (braille(bits), color)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Sample for `slot` of `slots`, right-aligned so the newest sample is in
+/// the last slot. 0 before the data starts and `None` on a gap.
+fn sample(
+    data: &VecDeque<u64>,
+    gaps: Option<&VecDeque<bool>>,
+    slot: usize,
+    slots: usize,
+) -> Option<u64> {
+    let back = slots - 1 - slot;
+    let Some(idx) = data.len().checked_sub(back + 1) else {
+        return Some(0);
+    };
+    if gaps.and_then(|gaps| gaps.get(idx)) == Some(&true) {
+        return None;
+    }
+    data.get(idx).copied()
+}
+
+fn braille(bits: u32) -> char {
+    char::from_u32(0x2800 + bits).unwrap_or(' ')
+}
+
+/// Short scale label, e.g. `850`, `12k`, `3.4M`.
+fn axis_label(value: u64) -> String {
+    let v = value as f64;
+    match value {
+        0..1_000 => value.to_string(),
+        1_000..10_000 => format!("{:.1}k", v / 1e3),
+        10_000..1_000_000 => format!("{:.0}k", v / 1e3),
+        1_000_000..10_000_000 => format!("{:.1}M", v / 1e6),
+        _ => format!("{:.0}M", v / 1e6),
     }
 }
 
@@ -132,10 +415,8 @@ where
         // Adjust height to fit available space
         let actual_height = (self.height as usize).min(area.height as usize);
         let graph = Self {
-            data: self.data,
-            max: self.max,
             height: actual_height as u16,
-            color_fn: self.color_fn,
+            ..self
         };
         let lines = graph.build_grid(area.width as usize);
 
@@ -173,28 +454,3 @@ pub fn make_color_fn(red_cap: u64, dynamic: bool) -> impl Fn(u64, u64) -> Color
         }
     }
 }
-
-// Keep Dotline for backwards compatibility but mark as deprecated
-#[allow(dead_code)]
-pub struct Dotline<'a, F>
-where
-    F: Fn(u64, u64) -> Color,
-{
-    data: &'a VecDeque<u64>,
-    max: u64,
-    color_fn: F,
-}
-
-#[allow(dead_code)]
-impl<'a, F> Dotline<'a, F>
-where
-    F: Fn(u64, u64) -> Color,
-{
-    pub fn new(data: &'a VecDeque<u64>, max: u64, color_fn: F) -> Self {
-        Self {
-            data,
-            max,
-            color_fn,
-        }
-    }
-}
@@ -0,0 +1,624 @@
+//! Entity-relationship diagram of a schema.
+//!
+//! Tables are drawn as boxes on a grid, most-connected first so related
+//! tables end up near each other. Foreign keys are routed through the empty
+//! gutters and channels between grid cells, so a line never crosses a box.
+//! The same model exports as Mermaid or Graphviz DOT text.
+
+use crate::event::DbSchema;
+use crate::export::html_escape;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Columns of empty space left and right of every grid column.
+const GUTTER: usize = 8;
+/// Rows of empty space above every grid row.
+const CHANNEL: usize = 4;
+
+/// How much of each table is drawn; zooming steps through these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErdDetail {
+    /// Table names only.
+    Names,
+    /// Primary- and foreign-key columns.
+    Keys,
+    /// Every column with its type.
+    Columns,
+}
+
+impl ErdDetail {
+    pub fn zoom_in(self) -> Self {
+        match self {
+            Self::Names => Self::Keys,
+            Self::Keys | Self::Columns => Self::Columns,
+        }
+    }
+
+    pub fn zoom_out(self) -> Self {
+        match self {
+            Self::Columns => Self::Keys,
+            Self::Keys | Self::Names => Self::Names,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Names => "names",
+            Self::Keys => "keys",
+            Self::Columns => "columns",
+        }
+    }
+}
+
+/// What a canvas cell belongs to, so the UI can colour it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    Empty,
+    Border,
+    Title,
+    PrimaryKey,
+    ForeignKey,
+    Column,
+    Edge,
+}
+
+/// Rendered diagram: a grid of single-width characters.
+#[derive(Debug, Clone)]
+pub struct ErdCanvas {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<(char, CellKind)>,
+}
+
+impl ErdCanvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![(' ', CellKind::Empty); width * height],
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[(char, CellKind)] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    fn set(&mut self, x: usize, y: usize, ch: char, kind: CellKind) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = (ch, kind);
+        }
+    }
+
+    fn text(&mut self, x: usize, y: usize, text: &str, kind: CellKind) {
+        for (i, ch) in text.chars().enumerate() {
+            self.set(x + i, y, ch, kind);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ErdColumn {
+    name: String,
+    data_type: String,
+    is_primary_key: bool,
+    is_foreign_key: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ErdTable {
+    name: String,
+    columns: Vec<ErdColumn>,
+}
+
+/// One column line inside a table box.
+struct BoxRow<'a> {
+    column: &'a str,
+    text: String,
+    kind: CellKind,
+}
+
+/// Foreign key between two tables of the schema.
+#[derive(Debug, Clone)]
+struct ErdEdge {
+    name: String,
+    from: usize,
+    columns: Vec<String>,
+    to: usize,
+    ref_columns: Vec<String>,
+}
+
+/// Tables and relationships of one schema.
+#[derive(Debug, Clone)]
+pub struct Erd {
+    pub schema: String,
+    tables: Vec<ErdTable>,
+    edges: Vec<ErdEdge>,
+}
+
+impl Erd {
+    pub fn from_schema(schema: &DbSchema) -> Self {
+        let index: HashMap<&str, usize> = schema
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.name.as_str(), i))
+            .collect();
+
+        let mut edges = Vec::new();
+        let tables = schema
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(from, table)| {
+                for fk in &table.foreign_keys {
+                    // Keys into other schemas still mark the column, but get no line
+                    if fk.ref_schema != schema.name {
+                        continue;
+                    }
+                    if let Some(&to) = index.get(fk.ref_table.as_str()) {
+                        edges.push(ErdEdge {
+                            name: fk.name.clone(),
+                            from,
+                            columns: fk.columns.clone(),
+                            to,
+                            ref_columns: fk.ref_columns.clone(),
+                        });
+                    }
+                }
+                ErdTable {
+                    name: table.name.clone(),
+                    columns: table
+                        .columns
+                        .iter()
+                        .map(|c| ErdColumn {
+                            name: c.name.clone(),
+                            data_type: c.data_type.clone(),
+                            is_primary_key: c.is_primary_key,
+                            is_foreign_key: table
+                                .foreign_keys
+                                .iter()
+                                .any(|fk| fk.columns.contains(&c.name)),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Self {
+            schema: schema.name.clone(),
+            tables,
+            edges,
+        }
+    }
+
+    pub fn table_count(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn relationship_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Table order for the grid: most-connected table first, then greedily
+    /// whichever unplaced table has the most links to those already placed.
+    fn placement_order(&self) -> Vec<usize> {
+        let n = self.tables.len();
+        let mut degree = vec![0usize; n];
+        let mut links = vec![vec![0usize; n]; n];
+        for edge in self.edges.iter().filter(|e| e.from != e.to) {
+            degree[edge.from] += 1;
+            degree[edge.to] += 1;
+            links[edge.from][edge.to] += 1;
+            links[edge.to][edge.from] += 1;
+        }
+
+        let mut order = Vec::with_capacity(n);
+        let mut placed = vec![false; n];
+        while order.len() < n {
+            let next = (0..n)
+                .filter(|&i| !placed[i])
+                .max_by_key(|&i| {
+                    let linked: usize = order.iter().map(|&p: &usize| links[p][i]).sum();
+                    // Ties go to the alphabetically first table
+                    (linked, degree[i], std::cmp::Reverse(&self.tables[i].name))
+                })
+                .unwrap_or(0);
+            placed[next] = true;
+            order.push(next);
+        }
+        order
+    }
+
+    /// Rows of a table box (without borders) for the given detail level.
+    fn box_rows(table: &ErdTable, detail: ErdDetail) -> Vec<BoxRow<'_>> {
+        let columns: Vec<&ErdColumn> = match detail {
+            ErdDetail::Names => Vec::new(),
+            ErdDetail::Keys => table
+                .columns
+                .iter()
+                .filter(|c| c.is_primary_key || c.is_foreign_key)
+                .collect(),
+            ErdDetail::Columns => table.columns.iter().collect(),
+        };
+        let name_width = columns
+            .iter()
+            .map(|c| c.name.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        columns
+            .into_iter()
+            .map(|c| {
+                let (marker, kind) = if c.is_primary_key {
+                    ("PK", CellKind::PrimaryKey)
+                } else if c.is_foreign_key {
+                    ("FK", CellKind::ForeignKey)
+                } else {
+                    ("  ", CellKind::Column)
+                };
+                let text = if detail == ErdDetail::Columns {
+                    format!("{marker} {:<name_width$}  {}", c.name, c.data_type)
+                } else {
+                    format!("{marker} {}", c.name)
+                };
+                BoxRow {
+                    column: &c.name,
+                    text,
+                    kind,
+                }
+            })
+            .collect()
+    }
+
+    /// Lay the schema out and draw it.
+    pub fn render(&self, detail: ErdDetail) -> ErdCanvas {
+        let n = self.tables.len();
+        if n == 0 {
+            return ErdCanvas::new(0, 0);
+        }
+
+        let rows: Vec<Vec<BoxRow>> = self
+            .tables
+            .iter()
+            .map(|t| Self::box_rows(t, detail))
+            .collect();
+        // Borders plus one space of padding either side
+        let box_width: Vec<usize> = self
+            .tables
+            .iter()
+            .zip(&rows)
+            .map(|(t, r)| {
+                let content = r.iter().map(|row| row.text.chars().count());
+                content.chain([t.name.chars().count()]).max().unwrap_or(0) + 4
+            })
+            .collect();
+        let box_height: Vec<usize> = rows
+            .iter()
+            .map(|r| if r.is_empty() { 3 } else { r.len() + 4 })
+            .collect();
+
+        let grid_cols = (n as f64).sqrt().ceil() as usize;
+        let grid_rows = n.div_ceil(grid_cols);
+        let mut cell_of = vec![(0, 0); n];
+        let mut col_width = vec![0; grid_cols];
+        let mut row_height = vec![0; grid_rows];
+        for (slot, &table) in self.placement_order().iter().enumerate() {
+            let (r, c) = (slot / grid_cols, slot % grid_cols);
+            cell_of[table] = (r, c);
+            col_width[c] = col_width[c].max(box_width[table]);
+            row_height[r] = row_height[r].max(box_height[table]);
+        }
+
+        let mut col_x = Vec::with_capacity(grid_cols);
+        let mut x = GUTTER;
+        for w in &col_width {
+            col_x.push(x);
+            x += w + GUTTER;
+        }
+        let mut row_y = Vec::with_capacity(grid_rows);
+        let mut y = CHANNEL;
+        for h in &row_height {
+            row_y.push(y);
+            y += h + CHANNEL;
+        }
+
+        let mut canvas = ErdCanvas::new(x, y);
+        let origin = |t: usize| (col_x[cell_of[t].1], row_y[cell_of[t].0]);
+        // Gutter `g` runs down the left of grid column `g`; the last one
+        // closes off the right of the grid
+        let gutter_x = |g: usize| {
+            if g < grid_cols {
+                col_x[g] - GUTTER
+            } else {
+                col_x[g - 1] + col_width[g - 1]
+            }
+        };
+        // Row of a column inside its box, or the title row when it isn't shown
+        let anchor_row = |t: usize, columns: &[String]| {
+            let offset = columns
+                .first()
+                .and_then(|name| rows[t].iter().position(|row| row.column == name))
+                .map_or(1, |i| i + 3);
+            origin(t).1 + offset
+        };
+
+        let mut lines = LineGrid::new(canvas.width, canvas.height);
+        let mut exits = Vec::new();
+        for (i, edge) in self.edges.iter().enumerate() {
+            if edge.from == edge.to {
+                continue;
+            }
+            let ((_, from_col), (_, to_col)) = (cell_of[edge.from], cell_of[edge.to]);
+            let (from_x, _) = origin(edge.from);
+            let (to_x, to_y) = origin(edge.to);
+            let sy = anchor_row(edge.from, &edge.columns);
+            let ty = anchor_row(edge.to, &edge.ref_columns);
+            let lane_x = 2 + i % (GUTTER - 3);
+            let lane_y = 1 + i % (CHANNEL - 1);
+
+            // Leave towards the target; tables in the same column loop round
+            // their shared right gutter
+            let exit_right = to_col >= from_col;
+            let enter_left = to_col > from_col;
+            let (sx, exit_gutter) = if exit_right {
+                (from_x + box_width[edge.from] - 1, from_col + 1)
+            } else {
+                (from_x, from_col)
+            };
+            let (tx, entry_gutter) = if enter_left {
+                (to_x, to_col)
+            } else {
+                (to_x + box_width[edge.to] - 1, to_col + 1)
+            };
+            let gx1 = gutter_x(exit_gutter) + lane_x;
+            let gx2 = gutter_x(entry_gutter) + lane_x;
+
+            if exit_gutter == entry_gutter {
+                lines.horizontal(sx, gx1, sy);
+                lines.vertical(gx1, sy, ty);
+                lines.horizontal(gx1, tx, ty);
+            } else {
+                let cy = to_y - CHANNEL + lane_y;
+                lines.horizontal(sx, gx1, sy);
+                lines.vertical(gx1, sy, cy);
+                lines.horizontal(gx1, gx2, cy);
+                lines.vertical(gx2, cy, ty);
+                lines.horizontal(gx2, tx, ty);
+            }
+            exits.push((sx, sy, exit_right, tx, ty, enter_left));
+        }
+        lines.draw(&mut canvas);
+
+        for (t, table) in self.tables.iter().enumerate() {
+            let (bx, by) = origin(t);
+            draw_box(&mut canvas, bx, by, box_width[t], &table.name, &rows[t]);
+        }
+
+        // Tee where a line leaves a box, arrowhead where it arrives
+        for (sx, sy, exit_right, tx, ty, enter_left) in exits {
+            canvas.set(sx, sy, if exit_right { '├' } else { '┤' }, CellKind::Border);
+            if enter_left {
+                canvas.set(tx - 1, ty, '▶', CellKind::Edge);
+            } else {
+                canvas.set(tx + 1, ty, '◀', CellKind::Edge);
+            }
+        }
+        canvas
+    }
+
+    /// Mermaid `erDiagram` source.
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("erDiagram\n");
+        for table in &self.tables {
+            let _ = writeln!(out, "    {} {{", mermaid_name(&table.name));
+            for c in &table.columns {
+                let keys = match (c.is_primary_key, c.is_foreign_key) {
+                    (true, true) => " PK, FK",
+                    (true, false) => " PK",
+                    (false, true) => " FK",
+                    (false, false) => "",
+                };
+                let _ = writeln!(
+                    out,
+                    "        {} {}{keys}",
+                    mermaid_name(&c.data_type),
+                    mermaid_name(&c.name)
+                );
+            }
+            out.push_str("    }\n");
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "    {} ||--o{{ {} : \"{}\"",
+                mermaid_name(&self.tables[edge.to].name),
+                mermaid_name(&self.tables[edge.from].name),
+                edge.columns.join(", ").replace('"', "'")
+            );
+        }
+        out
+    }
+
+    /// Graphviz DOT source with one HTML-like record per table.
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph {} {{\n", dot_id(&self.schema));
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [shape=plain, fontname=\"monospace\"];\n\n");
+        for table in &self.tables {
+            let _ = writeln!(
+                out,
+                "    {} [label=<<table border=\"0\" cellborder=\"1\" cellspacing=\"0\">",
+                dot_id(&table.name)
+            );
+            let _ = writeln!(
+                out,
+                "        <tr><td bgcolor=\"lightgrey\"><b>{}</b></td></tr>",
+                html_escape(&table.name)
+            );
+            for c in &table.columns {
+                let marker = if c.is_primary_key {
+                    "PK "
+                } else if c.is_foreign_key {
+                    "FK "
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    out,
+                    "        <tr><td port={} align=\"left\">{marker}{} <i>{}</i></td></tr>",
+                    dot_id(&c.name),
+                    html_escape(&c.name),
+                    html_escape(&c.data_type)
+                );
+            }
+            out.push_str("    </table>>];\n");
+        }
+        if !self.edges.is_empty() {
+            out.push('\n');
+        }
+        for edge in &self.edges {
+            let port = |columns: &[String]| {
+                columns
+                    .first()
+                    .map_or(String::new(), |c| format!(":{}", dot_id(c)))
+            };
+            let _ = writeln!(
+                out,
+                "    {}{} -> {}{} [label={}];",
+                dot_id(&self.tables[edge.from].name),
+                port(&edge.columns),
+                dot_id(&self.tables[edge.to].name),
+                port(&edge.ref_columns),
+                dot_id(&edge.name)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn draw_box(
+    canvas: &mut ErdCanvas,
+    x: usize,
+    y: usize,
+    width: usize,
+    title: &str,
+    rows: &[BoxRow],
+) {
+    let inner = width - 2;
+    let rule = "─".repeat(inner);
+    canvas.text(x, y, &format!("┌{rule}┐"), CellKind::Border);
+    canvas.text(x, y + 1, "│", CellKind::Border);
+    canvas.text(x + 2, y + 1, title, CellKind::Title);
+    canvas.text(x + width - 1, y + 1, "│", CellKind::Border);
+    if rows.is_empty() {
+        canvas.text(x, y + 2, &format!("└{rule}┘"), CellKind::Border);
+        return;
+    }
+
+    canvas.text(x, y + 2, &format!("├{rule}┤"), CellKind::Border);
+    for (i, row) in rows.iter().enumerate() {
+        let row_y = y + 3 + i;
+        canvas.text(x, row_y, "│", CellKind::Border);
+        canvas.text(x + 2, row_y, &row.text, row.kind);
+        canvas.text(x + width - 1, row_y, "│", CellKind::Border);
+    }
+    canvas.text(
+        x,
+        y + 3 + rows.len(),
+        &format!("└{rule}┘"),
+        CellKind::Border,
+    );
+}
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// Connector lines as per-cell direction bits, so crossings and corners
+/// come out as the right box-drawing character.
+struct LineGrid {
+    width: usize,
+    bits: Vec<u8>,
+}
+
+impl LineGrid {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            bits: vec![0; width * height],
+        }
+    }
+
+    fn add(&mut self, x: usize, y: usize, bits: u8) {
+        if let Some(cell) = self.bits.get_mut(y * self.width + x) {
+            *cell |= bits;
+        }
+    }
+
+    fn horizontal(&mut self, x1: usize, x2: usize, y: usize) {
+        let (from, to) = (x1.min(x2), x1.max(x2));
+        for x in from..=to {
+            let mut bits = 0;
+            if x > from {
+                bits |= LEFT;
+            }
+            if x < to {
+                bits |= RIGHT;
+            }
+            self.add(x, y, bits);
+        }
+    }
+
+    fn vertical(&mut self, x: usize, y1: usize, y2: usize) {
+        let (from, to) = (y1.min(y2), y1.max(y2));
+        for y in from..=to {
+            let mut bits = 0;
+            if y > from {
+                bits |= UP;
+            }
+            if y < to {
+                bits |= DOWN;
+            }
+            self.add(x, y, bits);
+        }
+    }
+
+    fn draw(&self, canvas: &mut ErdCanvas) {
+        for (i, &bits) in self.bits.iter().enumerate() {
+            let ch = match bits {
+                0 => continue,
+                b if b == UP | DOWN | LEFT | RIGHT => '┼',
+                b if b == UP | DOWN | RIGHT => '├',
+                b if b == UP | DOWN | LEFT => '┤',
+                b if b == LEFT | RIGHT | DOWN => '┬',
+                b if b == LEFT | RIGHT | UP => '┴',
+                b if b == DOWN | RIGHT => '┌',
+                b if b == DOWN | LEFT => '┐',
+                b if b == UP | RIGHT => '└',
+                b if b == UP | LEFT => '┘',
+                b if b & (UP | DOWN) != 0 => '│',
+                _ => '─',
+            };
+            canvas.set(i % self.width, i / self.width, ch, CellKind::Edge);
+        }
+    }
+}
+
+/// Mermaid identifiers only allow word characters and hyphens.
+fn mermaid_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -1,8 +1,15 @@
-use crate::app::DbType;
+use crate::app::{DbType, TableFilter};
+use crate::import::ImportReport;
+use crate::monitor::{
+    ConnectionUsage, HealthReport, LockWait, QueryStats, ServerCounters, ServerSession,
+};
+use crate::schema_diff::DiffEntry;
 use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::event::Event as CrosstermEvent;
 use sqlx::AnyPool;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
@@ -24,6 +31,7 @@ pub enum Event {
 #[derive(Debug, Clone)]
 pub struct TableDataResult {
     pub table_name: String,
+    pub filter: Option<TableFilter>,
     pub columns: Vec<String>,
     pub rows: Vec<Vec<String>>,
     pub total_count: i64,
@@ -48,32 +56,169 @@ pub struct StatsUpdate {
     pub total_rows: i64,
 }
 
+/// Connection pool state sampled on each sparkline tick.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoolMetrics {
+    pub size: u32,
+    pub idle: u32,
+    pub max: u32,
+    /// How long it took to check a connection out of the pool.
+    pub acquire_ms: u64,
+    /// The checkout timed out: every connection is busy and `acquire_ms`
+    /// is only a lower bound.
+    pub saturated: bool,
+    /// Connections on the whole server, when they could be read.
+    pub server: Option<ConnectionUsage>,
+}
+
+impl PoolMetrics {
+    pub fn in_use(&self) -> u32 {
+        self.size.saturating_sub(self.idle)
+    }
+}
+
 /// Column information from information_schema.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct DbColumn {
     pub name: String,
     pub data_type: String,
+    /// Full declared type, e.g. `character varying(255)` or `int unsigned`,
+    /// as needed to recreate the column.
+    #[serde(default)]
+    pub column_type: String,
     pub is_nullable: bool,
     pub is_primary_key: bool,
     pub ordinal_position: i32,
+    /// Description from `COMMENT ON COLUMN` or MySQL's column comment.
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
-/// Table with its columns.
-#[derive(Debug, Clone)]
+/// Index on a table. `columns` holds column names or, for expression
+/// indexes, the indexed expressions.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DbIndex {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub is_unique: bool,
+    pub is_primary: bool,
+    /// Access method, e.g. `btree`, `gin`, `hash` or `fulltext`.
+    pub method: String,
+}
+
+/// Foreign key from a table to a referenced table.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DbForeignKey {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub ref_schema: String,
+    pub ref_table: String,
+    pub ref_columns: Vec<String>,
+    pub on_update: String,
+    pub on_delete: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ConstraintKind {
+    Check,
+    Unique,
+}
+
+/// Check or unique constraint.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DbConstraint {
+    pub name: String,
+    pub kind: ConstraintKind,
+    /// Constraint definition, e.g. `CHECK (price > 0)` or `UNIQUE (email)`.
+    pub definition: String,
+}
+
+/// Table with its columns, indexes and keys.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct DbTable {
     pub name: String,
     pub columns: Vec<DbColumn>,
+    pub indexes: Vec<DbIndex>,
+    pub foreign_keys: Vec<DbForeignKey>,
+    pub constraints: Vec<DbConstraint>,
+    /// Size estimates; `None` for plain views.
+    pub stats: Option<TableStats>,
+    /// Description from `COMMENT ON` or MySQL's table comment.
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
-/// Schema with its tables.
-#[derive(Debug, Clone)]
+/// Approximate size of a table, from catalog statistics rather than a scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TableStats {
+    /// Planner row estimate; `None` until the table has been analyzed.
+    pub row_estimate: Option<i64>,
+    /// Bytes on disk including indexes (and TOAST on Postgres).
+    pub total_bytes: i64,
+    pub index_bytes: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RoutineKind {
+    Function,
+    Procedure,
+}
+
+impl RoutineKind {
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Self::Function => "FUNCTION",
+            Self::Procedure => "PROCEDURE",
+        }
+    }
+}
+
+/// Stored function or procedure. The body is fetched on demand.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DbRoutine {
+    pub name: String,
+    /// Unique handle used to fetch the definition: the `pg_proc` oid on
+    /// Postgres, the routine's `specific_name` on MySQL.
+    pub specific_name: String,
+    pub kind: RoutineKind,
+    pub arguments: String,
+    /// Return type; empty for procedures.
+    pub result: String,
+    pub language: String,
+}
+
+/// Sequence.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DbSequence {
+    pub name: String,
+    pub data_type: String,
+    /// Last value handed out, if the sequence has been used.
+    pub last_value: Option<String>,
+}
+
+/// User-defined type: enum, composite, domain or range.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DbUserType {
+    pub name: String,
+    pub kind: String,
+    /// Enum labels, composite fields or the domain's base type.
+    pub detail: String,
+}
+
+/// Schema with its tables and other objects.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct DbSchema {
     pub name: String,
     pub tables: Vec<DbTable>,
+    pub views: Vec<DbTable>,
+    pub materialized_views: Vec<DbTable>,
+    pub routines: Vec<DbRoutine>,
+    pub sequences: Vec<DbSequence>,
+    pub types: Vec<DbUserType>,
 }
 
 /// Full database structure.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DatabaseStructure {
     pub schemas: Vec<DbSchema>,
 }
@@ -88,15 +233,67 @@ pub enum AppEvent {
     /// Tables loaded from database (legacy, kept for compatibility).
     TablesLoaded(Vec<String>),
     /// Full database schema structure loaded.
-    SchemaLoaded(DatabaseStructure),
+    SchemaLoaded(Result<DatabaseStructure, String>),
+    /// Server sessions for the sessions view.
+    SessionsLoaded(Result<Vec<ServerSession>, String>),
+    /// Health report for the health view.
+    HealthLoaded(Result<HealthReport, String>),
+    /// Statement statistics for the top queries view.
+    TopQueriesLoaded(Result<QueryStats, String>),
+    /// Lock waits plus the sessions involved, for the locks view.
+    LocksLoaded(Result<(Vec<LockWait>, Vec<ServerSession>), String>),
+    /// A cancel or terminate finished; the message describes it.
+    SessionActionDone(Result<String, String>),
+    /// Differences from one saved connection's schema to another's.
+    SchemaDiffLoaded {
+        source: String,
+        target: String,
+        result: Result<(DbType, Vec<DiffEntry>), String>,
+    },
+    /// Periodic refresh found catalog changes: `schemas` holds the refetched
+    /// ones and `present` every schema that still exists, in display order.
+    SchemasRefreshed {
+        schemas: Vec<DbSchema>,
+        present: Vec<String>,
+    },
+    /// Fresh size estimates for every table, keyed by (schema, table).
+    TableStatsLoaded(HashMap<(String, String), TableStats>),
     /// Table data loaded.
     TableDataLoaded(Result<TableDataResult, String>),
     /// SQL query execution result.
     QueryExecuted(Result<QueryResult, String>),
     /// Stats updated from background task.
     StatsUpdated(StatsUpdate),
-    /// Sparkline tick (every 1 second).
-    SparklineTick { pool_size: u32 },
+    /// Sparkline tick (every 1 second), with server-wide rates when the
+    /// server series is shown and a previous sample exists.
+    SparklineTick {
+        pool: PoolMetrics,
+        throughput: Option<ServerCounters>,
+    },
+    /// A configured graph query was polled; `index` is into the custom
+    /// queries.
+    GraphQueryPolled {
+        index: usize,
+        result: Result<u64, String>,
+    },
+    /// Rows inserted so far by a running import.
+    ImportProgress { inserted: usize },
+    /// Import finished.
+    ImportFinished(Result<ImportReport, String>),
+    /// Rows written so far by a running full export.
+    ExportProgress { rows_written: usize },
+    /// Full export finished with the output path and row count.
+    ExportFinished(Result<(PathBuf, usize), String>),
+    /// Object definition (e.g. a function body) loaded for the definition view.
+    DefinitionLoaded {
+        title: String,
+        result: Result<String, String>,
+    },
+    /// Referencing-row counts for the references modal, in entry order.
+    ReferenceCountsLoaded {
+        title: String,
+        counts: Vec<Result<i64, String>>,
+    },
 }
 
 /// Terminal event handler.
@@ -0,0 +1,583 @@
+//! Result-set export to files.
+//!
+//! [`ExportWriter`] writes rows incrementally so the same code serves both
+//! exports of the rows already in memory and full exports streamed from
+//! the server in batches.
+
+use crate::app::{DbType, quote_ident, quote_qualified};
+use rust_xlsxwriter::{ExcelDateTime, Format, FormatBorder, Workbook, Worksheet, XlsxError};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Default export path; placeholders are expanded by [`expand_path_template`].
+pub const DEFAULT_PATH_TEMPLATE: &str = "{table}_{timestamp}.{ext}";
+
+/// Rows streamed from the export query before they are handed to the
+/// writer and progress is reported.
+pub const EXPORT_BATCH_SIZE: usize = 1000;
+
+/// Rows per multi-row `INSERT` statement in SQL exports.
+const SQL_INSERT_BATCH_ROWS: usize = 100;
+
+/// Stand-in `INSERT` target for rows that don't come from a single table.
+pub const PLACEHOLDER_TABLE: &str = "query_result";
+
+/// Heads SQL written against [`PLACEHOLDER_TABLE`].
+const PLACEHOLDER_NOTE: &str = "-- These rows come from a query, not a single table: \
+     replace \"query_result\" with the target table before running.";
+
+/// Widest column, in characters, sized automatically in xlsx exports.
+const XLSX_MAX_COLUMN_WIDTH: usize = 60;
+
+/// Longest string Excel accepts in a single cell.
+const XLSX_MAX_STRING_CHARS: usize = 32_767;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Tsv,
+    Json,
+    JsonLines,
+    Markdown,
+    SqlInsert,
+    Xlsx,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Json => "json",
+            Self::JsonLines => "jsonl",
+            Self::Markdown => "md",
+            Self::SqlInsert => "sql",
+            Self::Xlsx => "xlsx",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// Expand an export path template.
+///
+/// Supported placeholders: `{table}`, `{timestamp}`, `{date}` and `{ext}`.
+/// A leading `~/` is expanded to the home directory.
+pub fn expand_path_template(template: &str, table: &str, format: ExportFormat) -> PathBuf {
+    let now = chrono::Local::now();
+    let table: String = table
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let expanded = template
+        .trim()
+        .replace("{table}", &table)
+        .replace("{timestamp}", &now.format("%Y%m%d_%H%M%S").to_string())
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{ext}", format.extension());
+
+    match expanded.strip_prefix("~/") {
+        Some(rest) => directories::BaseDirs::new()
+            .map(|dirs| dirs.home_dir().join(rest))
+            .unwrap_or_else(|| PathBuf::from(&expanded)),
+        None => PathBuf::from(expanded),
+    }
+}
+
+/// Incremental writer for a single export file.
+pub struct ExportWriter {
+    format: ExportFormat,
+    out: BufWriter<File>,
+    rows_written: usize,
+    /// Quoted `table (col, ...)` target for SQL exports.
+    insert_target: String,
+    db_type: DbType,
+    /// Rows in the currently open `INSERT` statement.
+    rows_in_statement: usize,
+    /// Workbook being built for [`ExportFormat::Xlsx`]; saved into `out` on finish.
+    xlsx: Option<XlsxSheet>,
+}
+
+/// Worksheet state for xlsx exports.
+struct XlsxSheet {
+    workbook: Workbook,
+    /// Widest value seen per column, in characters.
+    widths: Vec<usize>,
+    date: Format,
+    datetime: Format,
+}
+
+impl ExportWriter {
+    /// Create the file (and any missing parent directories) and write the header.
+    ///
+    /// `table_name` is used as the target of [`ExportFormat::SqlInsert`] and
+    /// as the sheet or page title of [`ExportFormat::Xlsx`] and
+    /// [`ExportFormat::Html`]; `db_type` only affects SQL quoting.
+    pub fn create(
+        path: &Path,
+        format: ExportFormat,
+        columns: &[String],
+        table_name: &str,
+        db_type: DbType,
+    ) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut writer = Self {
+            format,
+            out: BufWriter::new(File::create(path)?),
+            rows_written: 0,
+            insert_target: format!(
+                "{} ({})",
+                quote_qualified(table_name, db_type),
+                columns
+                    .iter()
+                    .map(|c| quote_ident(c, db_type))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            db_type,
+            rows_in_statement: 0,
+            xlsx: None,
+        };
+        writer.write_header(columns, table_name)?;
+        Ok(writer)
+    }
+
+    fn write_header(&mut self, columns: &[String], table_name: &str) -> std::io::Result<()> {
+        let f = &mut self.out;
+        match self.format {
+            ExportFormat::Csv => writeln!(
+                f,
+                "{}",
+                columns
+                    .iter()
+                    .map(|c| csv_quote(c))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            ExportFormat::Tsv => writeln!(f, "{}", columns.join("\t")),
+            ExportFormat::Json => write!(f, "["),
+            ExportFormat::SqlInsert if table_name == PLACEHOLDER_TABLE => {
+                writeln!(f, "{PLACEHOLDER_NOTE}")
+            }
+            ExportFormat::JsonLines | ExportFormat::SqlInsert => Ok(()),
+            ExportFormat::Markdown => {
+                writeln!(f, "| {} |", columns.join(" | "))?;
+                writeln!(
+                    f,
+                    "| {} |",
+                    columns
+                        .iter()
+                        .map(|_| "---")
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                )
+            }
+            ExportFormat::Xlsx => {
+                self.xlsx = Some(XlsxSheet::new(columns, table_name).map_err(xlsx_error)?);
+                Ok(())
+            }
+            ExportFormat::Html => {
+                let title = html_escape(table_name);
+                writeln!(f, "<!DOCTYPE html>")?;
+                writeln!(f, "<html>\n<head>\n<meta charset=\"utf-8\">")?;
+                writeln!(f, "<title>{title}</title>\n<style>{HTML_STYLE}</style>")?;
+                writeln!(
+                    f,
+                    "</head>\n<body>\n<h1>{title}</h1>\n<table>\n<thead>\n<tr>"
+                )?;
+                for c in columns {
+                    writeln!(f, "  <th>{}</th>", html_escape(c))?;
+                }
+                writeln!(f, "</tr>\n</thead>\n<tbody>")
+            }
+        }
+    }
+
+    /// Append a batch of rows.
+    pub fn write_rows(&mut self, columns: &[String], rows: &[Vec<String>]) -> std::io::Result<()> {
+        for row in rows {
+            let f = &mut self.out;
+            match self.format {
+                ExportFormat::Csv => writeln!(
+                    f,
+                    "{}",
+                    row.iter()
+                        .map(|v| csv_quote(v))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?,
+                ExportFormat::Tsv => writeln!(f, "{}", row.join("\t"))?,
+                ExportFormat::Json => {
+                    let fields: Vec<String> = columns
+                        .iter()
+                        .zip(row.iter())
+                        .map(|(k, v)| format!("  {}: {}", json_string(k), json_value(v)))
+                        .collect();
+                    let sep = if self.rows_written == 0 { "\n" } else { ",\n" };
+                    write!(f, "{sep}{{\n{}\n}}", fields.join(",\n"))?;
+                }
+                ExportFormat::JsonLines => writeln!(f, "{}", row_to_json(columns, row))?,
+                ExportFormat::Markdown => writeln!(f, "| {} |", row.join(" | "))?,
+                ExportFormat::SqlInsert => {
+                    let values: Vec<String> =
+                        row.iter().map(|v| sql_literal(v, self.db_type)).collect();
+                    if self.rows_in_statement == 0 {
+                        write!(f, "INSERT INTO {} VALUES\n  ", self.insert_target)?;
+                    } else {
+                        write!(f, ",\n  ")?;
+                    }
+                    write!(f, "({})", values.join(", "))?;
+                    self.rows_in_statement += 1;
+                    if self.rows_in_statement == SQL_INSERT_BATCH_ROWS {
+                        writeln!(f, ";")?;
+                        self.rows_in_statement = 0;
+                    }
+                }
+                ExportFormat::Xlsx => {
+                    if let Some(sheet) = self.xlsx.as_mut() {
+                        // Row 0 holds the header
+                        let row_index = u32::try_from(self.rows_written + 1)
+                            .map_err(|_| std::io::Error::other("too many rows for a worksheet"))?;
+                        sheet.write_row(row_index, row).map_err(xlsx_error)?;
+                    }
+                }
+                ExportFormat::Html => {
+                    write!(f, "<tr>")?;
+                    for v in row {
+                        if v == "NULL" {
+                            write!(f, "<td class=\"null\">NULL</td>")?;
+                        } else if v.parse::<f64>().is_ok() {
+                            write!(f, "<td class=\"num\">{}</td>", html_escape(v))?;
+                        } else {
+                            write!(f, "<td>{}</td>", html_escape(v))?;
+                        }
+                    }
+                    writeln!(f, "</tr>")?;
+                }
+            }
+            self.rows_written += 1;
+        }
+        Ok(())
+    }
+
+    pub fn rows_written(&self) -> usize {
+        self.rows_written
+    }
+
+    /// Write any trailer, flush, and return the number of rows written.
+    pub fn finish(mut self) -> std::io::Result<usize> {
+        match self.format {
+            ExportFormat::Json => writeln!(self.out, "\n]")?,
+            ExportFormat::SqlInsert if self.rows_in_statement > 0 => writeln!(self.out, ";")?,
+            ExportFormat::Xlsx => {
+                if let Some(sheet) = self.xlsx.take() {
+                    sheet.save(&mut self.out).map_err(xlsx_error)?;
+                }
+            }
+            ExportFormat::Html => writeln!(
+                self.out,
+                "</tbody>\n</table>\n<p class=\"meta\">{} rows · exported {}</p>\n</body>\n</html>",
+                self.rows_written,
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+            )?,
+            _ => {}
+        }
+        self.out.flush()?;
+        Ok(self.rows_written)
+    }
+}
+
+impl XlsxSheet {
+    fn new(columns: &[String], table_name: &str) -> Result<Self, XlsxError> {
+        let mut workbook = Workbook::new();
+        let header = Format::new()
+            .set_bold()
+            .set_border_bottom(FormatBorder::Thin);
+        let worksheet = workbook.add_worksheet_with_constant_memory();
+        // Sheet names are limited to 31 characters and a few reserved symbols;
+        // fall back to the default name rather than failing the export.
+        let sheet_name: String = table_name
+            .chars()
+            .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+            .take(31)
+            .collect();
+        let _ = worksheet.set_name(sheet_name);
+        for (col, name) in columns.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, name, &header)?;
+        }
+        worksheet.set_freeze_panes(1, 0)?;
+
+        Ok(Self {
+            workbook,
+            widths: columns.iter().map(|c| c.chars().count()).collect(),
+            date: Format::new().set_num_format("yyyy-mm-dd"),
+            datetime: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+        })
+    }
+
+    fn worksheet(&mut self) -> Result<&mut Worksheet, XlsxError> {
+        self.workbook.worksheet_from_index(0)
+    }
+
+    /// Write one row, storing numbers, booleans and dates as typed cells.
+    /// `NULL` cells are left blank.
+    fn write_row(&mut self, row_index: u32, row: &[String]) -> Result<(), XlsxError> {
+        let (date, datetime) = (self.date.clone(), self.datetime.clone());
+        for (col, value) in row.iter().enumerate() {
+            if let Some(width) = self.widths.get_mut(col) {
+                *width = (*width).max(value.chars().count());
+            }
+            let col = col as u16;
+            let worksheet = self.worksheet()?;
+            if value == "NULL" {
+                continue;
+            }
+            if let Some(n) = xlsx_number(value) {
+                worksheet.write_number(row_index, col, n)?;
+            } else if let Some(b) = xlsx_bool(value) {
+                worksheet.write_boolean(row_index, col, b)?;
+            } else if let Some((dt, has_time)) = xlsx_datetime(value) {
+                let format = if has_time { &datetime } else { &date };
+                worksheet.write_datetime_with_format(row_index, col, dt, format)?;
+            } else if value.chars().count() > XLSX_MAX_STRING_CHARS {
+                let truncated: String = value.chars().take(XLSX_MAX_STRING_CHARS).collect();
+                worksheet.write_string(row_index, col, truncated)?;
+            } else {
+                worksheet.write_string(row_index, col, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Size the columns to their content and write the workbook to `out`.
+    fn save<W: Write + std::io::Seek + Send>(mut self, out: W) -> Result<(), XlsxError> {
+        let widths = std::mem::take(&mut self.widths);
+        let worksheet = self.worksheet()?;
+        for (col, width) in widths.into_iter().enumerate() {
+            let width = (width + 2).min(XLSX_MAX_COLUMN_WIDTH);
+            worksheet.set_column_width(col as u16, width as f64)?;
+        }
+        self.workbook.save_to_writer(out)
+    }
+}
+
+fn xlsx_error(e: XlsxError) -> std::io::Error {
+    std::io::Error::other(e)
+}
+
+/// Parse a cell as a number, leaving values that would lose information
+/// (leading zeros, integers beyond f64 precision) as text.
+fn xlsx_number(value: &str) -> Option<f64> {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if !digits.starts_with(|c: char| c.is_ascii_digit())
+        || (digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0."))
+    {
+        return None;
+    }
+    if let Ok(i) = value.parse::<i64>() {
+        return (i.unsigned_abs() < (1 << 53)).then_some(i as f64);
+    }
+    value.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+fn xlsx_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" | "TRUE" => Some(true),
+        "false" | "FALSE" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse a date or timezone-less timestamp; the flag is true when it has a time part.
+fn xlsx_datetime(value: &str) -> Option<(ExcelDateTime, bool)> {
+    use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+
+    if let Ok(d) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let dt = ExcelDateTime::from_ymd(d.year() as u16, d.month() as u8, d.day() as u8).ok()?;
+        return Some((dt, false));
+    }
+    let dt = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f"))
+        .ok()?;
+    let seconds = dt.second() as f64 + f64::from(dt.nanosecond()) / 1e9;
+    let excel = ExcelDateTime::from_ymd(dt.year() as u16, dt.month() as u8, dt.day() as u8)
+        .ok()?
+        .and_hms(dt.hour() as u16, dt.minute() as u8, seconds)
+        .ok()?;
+    Some((excel, true))
+}
+
+/// Inline stylesheet for HTML exports, so the file stands alone.
+const HTML_STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.3em; }
+table { border-collapse: collapse; font-size: 0.9em; }
+th, td { border: 1px solid #ddd; padding: 4px 10px; text-align: left; vertical-align: top; white-space: pre-wrap; }
+th { background: #f4f4f4; position: sticky; top: 0; }
+tr:nth-child(even) td { background: #fafafa; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+td.null { color: #aaa; font-style: italic; }
+.meta { color: #888; font-size: 0.8em; }
+";
+
+pub fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn csv_quote(s: &str) -> String {
+    if s.contains(',') || s.contains('\n') || s.contains('"') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Escape a string as a JSON string literal.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A cell as a JSON value: `NULL` cells become `null`, anything else a string.
+fn json_value(v: &str) -> String {
+    if v == "NULL" {
+        "null".to_string()
+    } else {
+        json_string(v)
+    }
+}
+
+/// Render one row as a single-line JSON object. `NULL` cells become `null`.
+pub fn row_to_json(columns: &[String], row: &[String]) -> String {
+    let fields: Vec<String> = columns
+        .iter()
+        .zip(row.iter())
+        .map(|(k, v)| format!("{}: {}", json_string(k), json_value(v)))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// Quote a value as a SQL string literal. `NULL` cells stay unquoted.
+pub fn sql_literal(value: &str, db_type: DbType) -> String {
+    if value == "NULL" {
+        return "NULL".to_string();
+    }
+    let escaped = value.replace('\'', "''");
+    match db_type {
+        DbType::Postgres => format!("'{escaped}'"),
+        // MySQL treats backslash as an escape character inside literals
+        DbType::MySQL => format!("'{}'", escaped.replace('\\', "\\\\")),
+    }
+}
+
+/// Render one row as an `INSERT INTO ... VALUES (...);` statement.
+pub fn row_to_insert(
+    table_name: &str,
+    columns: &[String],
+    row: &[String],
+    db_type: DbType,
+) -> String {
+    let cols: Vec<String> = columns.iter().map(|c| quote_ident(c, db_type)).collect();
+    let values: Vec<String> = row.iter().map(|v| sql_literal(v, db_type)).collect();
+    let note = if table_name == PLACEHOLDER_TABLE {
+        format!("{PLACEHOLDER_NOTE}\n")
+    } else {
+        String::new()
+    };
+    format!(
+        "{note}INSERT INTO {} ({}) VALUES ({});",
+        quote_qualified(table_name, db_type),
+        cols.join(", "),
+        values.join(", ")
+    )
+}
+
+/// Table to name as the `INSERT` target for the result of `query`: the one
+/// a plain single-table `SELECT` reads from, else [`PLACEHOLDER_TABLE`].
+pub fn insert_target(query: &str) -> String {
+    source_table(query).unwrap_or_else(|| PLACEHOLDER_TABLE.to_string())
+}
+
+/// `schema.table` or `table` after the only `FROM` of a `SELECT` with no
+/// joins, set operations or further tables, allowing an alias.
+fn source_table(query: &str) -> Option<String> {
+    let query = query.trim().trim_end_matches(';');
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let is = |word: &str, keywords: &[&str]| keywords.iter().any(|k| word.eq_ignore_ascii_case(k));
+    if !words.first().is_some_and(|w| is(w, &["SELECT"])) {
+        return None;
+    }
+    // A second FROM means a subquery
+    let mut froms = (0..words.len()).filter(|&i| is(words[i], &["FROM"]));
+    let from = froms.next()?;
+    if froms.next().is_some()
+        || words
+            .iter()
+            .any(|w| is(w, &["JOIN", "UNION", "INTERSECT", "EXCEPT"]))
+    {
+        return None;
+    }
+
+    let table = words.get(from + 1)?;
+    let quotes = table.matches(['"', '`']).count();
+    if table.contains([',', '(']) || quotes % 2 != 0 {
+        return None;
+    }
+    let rest = &words[from + 2..];
+    let alias_len = rest
+        .iter()
+        .position(|w| {
+            is(
+                w,
+                &[
+                    "WHERE", "GROUP", "ORDER", "HAVING", "LIMIT", "OFFSET", "FETCH", "FOR",
+                    "WINDOW",
+                ],
+            )
+        })
+        .unwrap_or(rest.len());
+    let alias = &rest[..alias_len];
+    let plain_alias = match alias {
+        [] => true,
+        [name] => !name.contains(','),
+        [as_, name] => is(as_, &["AS"]) && !name.contains(','),
+        _ => false,
+    };
+    plain_alias.then(|| table.replace(['"', '`'], ""))
+}
@@ -0,0 +1,218 @@
+//! Fuzzy search over the schema tree.
+//!
+//! Every schema, table, view and column becomes a candidate labelled with
+//! its dotted path (`public.orders.user_id`) and carrying the tree path
+//! that selects it, so a match can be revealed in the sidebar directly.
+
+use crate::app::{TreeCategory, TreeNodeId};
+use crate::event::{DatabaseStructure, DbTable};
+
+/// Most matches listed by the finder.
+pub const MAX_RESULTS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandidateKind {
+    Schema,
+    Table,
+    View,
+    Column,
+}
+
+/// A searchable tree node.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub kind: CandidateKind,
+    /// Dotted path shown in the finder and matched against.
+    pub label: String,
+    /// Char offset in `label` where the node's own name starts.
+    pub name_start: usize,
+    pub path: Vec<TreeNodeId>,
+}
+
+/// A candidate that matched the query.
+#[derive(Debug, Clone)]
+pub struct FinderMatch {
+    pub candidate: usize,
+    pub score: i64,
+    /// Char indices of `label` matched by the query, for highlighting.
+    pub positions: Vec<usize>,
+}
+
+/// Collect every schema, table, view and column of the structure.
+pub fn candidates(structure: &DatabaseStructure) -> Vec<Candidate> {
+    let mut out = Vec::new();
+    let root = vec![TreeNodeId::Root];
+
+    for schema in &structure.schemas {
+        let mut schema_path = root.clone();
+        schema_path.push(TreeNodeId::Schema(schema.name.clone()));
+        out.push(Candidate {
+            kind: CandidateKind::Schema,
+            label: schema.name.clone(),
+            name_start: 0,
+            path: schema_path.clone(),
+        });
+
+        for table in &schema.tables {
+            let mut path = schema_path.clone();
+            path.push(TreeNodeId::Table {
+                schema: schema.name.clone(),
+                table: table.name.clone(),
+            });
+            push_relation(&mut out, CandidateKind::Table, &schema.name, table, path);
+        }
+
+        for (category, views) in [
+            (TreeCategory::Views, &schema.views),
+            (TreeCategory::MaterializedViews, &schema.materialized_views),
+        ] {
+            for view in views {
+                let mut path = schema_path.clone();
+                path.push(TreeNodeId::Category {
+                    schema: schema.name.clone(),
+                    category,
+                });
+                path.push(TreeNodeId::View {
+                    schema: schema.name.clone(),
+                    view: view.name.clone(),
+                });
+                push_relation(&mut out, CandidateKind::View, &schema.name, view, path);
+            }
+        }
+    }
+    out
+}
+
+/// Add a table or view followed by its columns.
+fn push_relation(
+    out: &mut Vec<Candidate>,
+    kind: CandidateKind,
+    schema: &str,
+    table: &DbTable,
+    path: Vec<TreeNodeId>,
+) {
+    let label = format!("{schema}.{}", table.name);
+    let columns_start = label.chars().count() + 1;
+    out.push(Candidate {
+        kind,
+        label: label.clone(),
+        name_start: schema.chars().count() + 1,
+        path: path.clone(),
+    });
+
+    for column in &table.columns {
+        let mut column_path = path.clone();
+        column_path.push(TreeNodeId::Column {
+            schema: schema.to_string(),
+            table: table.name.clone(),
+            column: column.name.clone(),
+        });
+        out.push(Candidate {
+            kind: CandidateKind::Column,
+            label: format!("{label}.{}", column.name),
+            name_start: columns_start,
+            path: column_path,
+        });
+    }
+}
+
+/// Rank candidates against `query`, best first, keeping at most `limit`.
+/// An empty query matches everything in tree order.
+///
+/// Only a node's own name is searched unless the query contains a `.`, so
+/// `id` finds `id` columns rather than every table under `public`, while
+/// `us.id` still reaches `public.users.id`.
+pub fn search(candidates: &[Candidate], query: &str, limit: usize) -> Vec<FinderMatch> {
+    let whole_path = query.contains('.');
+    let mut matches: Vec<FinderMatch> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let from = if whole_path { 0 } else { c.name_start };
+            fuzzy_match(query, &c.label, c.name_start, from).map(|(score, positions)| FinderMatch {
+                candidate: i,
+                score,
+                positions,
+            })
+        })
+        .collect();
+    // Stable, so equal scores keep tree order
+    matches.sort_by_key(|m| std::cmp::Reverse(m.score));
+    matches.truncate(limit);
+    matches
+}
+
+/// Score `text` from char `from` onwards against `query` as a
+/// case-insensitive subsequence.
+///
+/// Consecutive runs and matches at word starts (after `.`, `_`, `-` or a
+/// space) score higher, as do matches inside the node's own name rather
+/// than its parents'. Every start position of the first query char is
+/// tried and the best alignment wins.
+pub fn fuzzy_match(
+    query: &str,
+    text: &str,
+    name_start: usize,
+    from: usize,
+) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    let text: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let mut best: Option<(i64, Vec<usize>)> = None;
+    for start in (from..lower.len()).filter(|&i| lower[i] == query[0]) {
+        let mut positions = vec![start];
+        let mut next = start + 1;
+        for &qc in &query[1..] {
+            match lower[next..].iter().position(|&c| c == qc) {
+                Some(offset) => {
+                    positions.push(next + offset);
+                    next += offset + 1;
+                }
+                None => break,
+            }
+        }
+        if positions.len() < query.len() {
+            // Later starts only have less text left to match against
+            break;
+        }
+
+        let score = score_positions(&text, &positions, name_start);
+        if best.as_ref().is_none_or(|(s, _)| score > *s) {
+            best = Some((score, positions));
+        }
+    }
+    best
+}
+
+fn score_positions(text: &[char], positions: &[usize], name_start: usize) -> i64 {
+    let mut score = 0i64;
+    let mut prev: Option<usize> = None;
+    for &pos in positions {
+        score += 1;
+        let at_boundary = pos == 0 || matches!(text[pos - 1], '.' | '_' | '-' | ' ');
+        if at_boundary {
+            score += 8;
+        }
+        if pos >= name_start {
+            score += 2;
+        }
+        match prev {
+            Some(p) if pos == p + 1 => score += 10,
+            Some(p) => score -= (pos - p - 1).min(8) as i64,
+            None => {}
+        }
+        prev = Some(pos);
+    }
+    // An exact name match beats a longer name that merely starts the same
+    if positions.first() == Some(&name_start) && positions.len() + name_start == text.len() {
+        score += 20;
+    }
+    score - (text.len() as i64 / 8)
+}
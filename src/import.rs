@@ -0,0 +1,433 @@
+//! CSV/TSV import into an existing table.
+//!
+//! The file is parsed and validated up front against the table's column
+//! types, so the preview can show problems before anything is written.
+//! Valid rows are then inserted in batches: `COPY ... FROM STDIN` on
+//! Postgres and multi-row `INSERT`s in a transaction on MySQL. A batch the
+//! server rejects is retried row by row to pin down the offending lines.
+
+use crate::app::{DbType, quote_ident};
+use crate::event::{AppEvent, DbTable, Event};
+use crate::export::sql_literal;
+use sqlx::AnyPool;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Rows sent per COPY / INSERT batch.
+pub const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Records shown in the preview table.
+pub const PREVIEW_ROWS: usize = 5;
+
+/// A row that passed validation, with its source line number.
+#[derive(Debug, Clone)]
+pub struct ImportRow {
+    pub line: usize,
+    /// One value per target column; `None` is NULL.
+    pub values: Vec<Option<String>>,
+}
+
+/// A row that was skipped, with its source line number and why.
+#[derive(Debug, Clone)]
+pub struct RejectedRow {
+    pub line: usize,
+    pub reason: String,
+}
+
+/// A previewed record: each cell's raw value and type-check error, if any.
+#[derive(Debug, Clone)]
+pub struct PreviewRow {
+    pub line: usize,
+    pub cells: Vec<(String, Option<String>)>,
+}
+
+/// A parsed and validated file, ready to insert.
+#[derive(Debug, Clone)]
+pub struct ImportPreview {
+    pub path: PathBuf,
+    /// Each file header and the table column it maps to.
+    pub mapping: Vec<(String, Option<String>)>,
+    /// Target columns, in the order of [`ImportRow::values`].
+    pub columns: Vec<String>,
+    /// NOT NULL table columns that no header maps to.
+    pub missing_required: Vec<String>,
+    pub preview: Vec<PreviewRow>,
+    pub rows: Vec<ImportRow>,
+    pub rejected: Vec<RejectedRow>,
+}
+
+/// Outcome of a finished import.
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    pub inserted: usize,
+    pub rejected: Vec<RejectedRow>,
+}
+
+/// Read, parse and validate `path` against `table`.
+///
+/// Headers are matched to columns case-insensitively; unmatched headers are
+/// ignored. Empty fields and the literal `NULL` are imported as NULL.
+pub fn prepare(path: &str, table: &DbTable) -> Result<ImportPreview, String> {
+    let path = PathBuf::from(path.trim());
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let contents = contents.trim_start_matches('\u{feff}');
+
+    let mut records = parse_delimited(contents, delimiter_for(&path)).into_iter();
+    let Some((_, headers)) = records.next() else {
+        return Err(format!("{} is empty", path.display()));
+    };
+
+    let mapping: Vec<(String, Option<usize>)> = headers
+        .iter()
+        .map(|h| {
+            let idx = table
+                .columns
+                .iter()
+                .position(|c| c.name.eq_ignore_ascii_case(h.trim()));
+            (h.clone(), idx)
+        })
+        .collect();
+    let mapped: Vec<(usize, usize)> = mapping
+        .iter()
+        .enumerate()
+        .filter_map(|(field, (_, col))| col.map(|c| (field, c)))
+        .collect();
+    if mapped.is_empty() {
+        return Err(format!("No header in {} matches a column", path.display()));
+    }
+
+    let missing_required = table
+        .columns
+        .iter()
+        .enumerate()
+        .filter(|(i, c)| !c.is_nullable && !mapped.iter().any(|(_, col)| col == i))
+        .map(|(_, c)| c.name.clone())
+        .collect();
+
+    let mut preview = Vec::new();
+    let mut rows = Vec::new();
+    let mut rejected = Vec::new();
+
+    for (line, fields) in records {
+        if fields.len() != headers.len() {
+            rejected.push(RejectedRow {
+                line,
+                reason: format!("expected {} fields, found {}", headers.len(), fields.len()),
+            });
+            continue;
+        }
+
+        let checks: Vec<Option<String>> = mapped
+            .iter()
+            .map(|&(field, col)| check_value(&fields[field], &table.columns[col]).err())
+            .collect();
+
+        if preview.len() < PREVIEW_ROWS {
+            preview.push(PreviewRow {
+                line,
+                cells: mapped
+                    .iter()
+                    .zip(&checks)
+                    .map(|(&(field, _), err)| (fields[field].clone(), err.clone()))
+                    .collect(),
+            });
+        }
+
+        if let Some((&(_, col), err)) = mapped
+            .iter()
+            .zip(&checks)
+            .find_map(|(m, e)| e.as_ref().map(|e| (m, e)))
+        {
+            rejected.push(RejectedRow {
+                line,
+                reason: format!("{}: {err}", table.columns[col].name),
+            });
+            continue;
+        }
+
+        rows.push(ImportRow {
+            line,
+            values: mapped
+                .iter()
+                .map(|&(field, _)| null_or_value(&fields[field]))
+                .collect(),
+        });
+    }
+
+    Ok(ImportPreview {
+        path,
+        mapping: mapping
+            .into_iter()
+            .map(|(h, col)| (h, col.map(|c| table.columns[c].name.clone())))
+            .collect(),
+        columns: mapped
+            .iter()
+            .map(|&(_, col)| table.columns[col].name.clone())
+            .collect(),
+        missing_required,
+        preview,
+        rows,
+        rejected,
+    })
+}
+
+fn delimiter_for(path: &Path) -> char {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("tsv") || ext.eq_ignore_ascii_case("tab") => '\t',
+        _ => ',',
+    }
+}
+
+fn null_or_value(field: &str) -> Option<String> {
+    if field.is_empty() || field == "NULL" {
+        None
+    } else {
+        Some(field.to_string())
+    }
+}
+
+/// Parse RFC 4180-style delimited text into records tagged with the line
+/// number each record starts on. Quoted fields may contain delimiters,
+/// doubled quotes and newlines.
+fn parse_delimited(text: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                if !(fields.len() == 1 && fields[0].is_empty()) {
+                    records.push((record_line, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                record_line = line;
+            }
+            c if c == delimiter => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((record_line, fields));
+    }
+    records
+}
+
+/// Check that `value` can be stored in `column`, based on its `data_type`.
+fn check_value(value: &str, column: &crate::event::DbColumn) -> Result<(), String> {
+    if null_or_value(value).is_none() {
+        return if column.is_nullable {
+            Ok(())
+        } else {
+            Err("NULL in NOT NULL column".to_string())
+        };
+    }
+
+    let value = value.trim();
+    let data_type = column.data_type.to_lowercase();
+    let ok = match data_type.as_str() {
+        "smallint" | "integer" | "int" | "bigint" | "tinyint" | "mediumint" | "int2" | "int4"
+        | "int8" => value.parse::<i64>().is_ok(),
+        "numeric" | "decimal" | "real" | "float" | "float4" | "float8" | "double" | "money" => {
+            value.parse::<f64>().is_ok()
+        }
+        "bool" | "boolean" => matches!(
+            value.to_lowercase().as_str(),
+            "true" | "false" | "t" | "f" | "1" | "0" | "yes" | "no" | "y" | "n" | "on" | "off"
+        ),
+        "date" => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        "timestamp" | "timestamptz" | "datetime" => is_timestamp(value),
+        "uuid" => is_uuid(value),
+        _ => true,
+    };
+
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("'{value}' is not a valid {}", column.data_type))
+    }
+}
+
+fn is_timestamp(value: &str) -> bool {
+    const FORMATS: &[&str] = &[
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+    ];
+    chrono::DateTime::parse_from_rfc3339(value).is_ok()
+        || chrono::DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%#z").is_ok()
+        || FORMATS
+            .iter()
+            .any(|f| chrono::NaiveDateTime::parse_from_str(value, f).is_ok())
+        || chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+fn is_uuid(value: &str) -> bool {
+    let value = value.trim_start_matches('{').trim_end_matches('}');
+    value.len() == 36
+        && value.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// Insert the validated rows of `preview` into `schema.table`, reporting
+/// progress after every batch.
+pub async fn run_import(
+    pool: &AnyPool,
+    database_url: &str,
+    db_type: DbType,
+    schema: &str,
+    table: &str,
+    preview: &ImportPreview,
+    sender: &UnboundedSender<Event>,
+) -> Result<ImportReport, String> {
+    let target = format!(
+        "{}.{} ({})",
+        quote_ident(schema, db_type),
+        quote_ident(table, db_type),
+        preview
+            .columns
+            .iter()
+            .map(|c| quote_ident(c, db_type))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let mut copy_conn = match db_type {
+        DbType::Postgres => {
+            use sqlx::Connection;
+            Some(
+                sqlx::postgres::PgConnection::connect(database_url)
+                    .await
+                    .map_err(|e| format!("Failed to open COPY connection: {e}"))?,
+            )
+        }
+        DbType::MySQL => None,
+    };
+
+    let mut report = ImportReport {
+        inserted: 0,
+        rejected: Vec::new(),
+    };
+
+    for batch in preview.rows.chunks(IMPORT_BATCH_SIZE) {
+        let result = match copy_conn.as_mut() {
+            Some(conn) => copy_batch(conn, &target, batch).await,
+            None => insert_batch(pool, &target, batch, db_type).await,
+        };
+
+        match result {
+            Ok(()) => report.inserted += batch.len(),
+            Err(e) => {
+                tracing::warn!("Import batch failed, retrying row by row: {}", e);
+                for row in batch {
+                    match insert_batch(pool, &target, std::slice::from_ref(row), db_type).await {
+                        Ok(()) => report.inserted += 1,
+                        Err(reason) => report.rejected.push(RejectedRow {
+                            line: row.line,
+                            reason,
+                        }),
+                    }
+                }
+            }
+        }
+
+        let _ = sender.send(Event::App(AppEvent::ImportProgress {
+            inserted: report.inserted,
+        }));
+    }
+
+    Ok(report)
+}
+
+/// Send one batch through `COPY ... FROM STDIN` in CSV format.
+async fn copy_batch(
+    conn: &mut sqlx::postgres::PgConnection,
+    target: &str,
+    batch: &[ImportRow],
+) -> Result<(), String> {
+    let mut data = String::new();
+    for row in batch {
+        let fields: Vec<String> = row
+            .values
+            .iter()
+            .map(|v| match v {
+                // Unquoted empty is NULL; quoted empty is an empty string
+                None => String::new(),
+                Some(v) => format!("\"{}\"", v.replace('"', "\"\"")),
+            })
+            .collect();
+        data.push_str(&fields.join(","));
+        data.push('\n');
+    }
+
+    let mut copy = conn
+        .copy_in_raw(&format!("COPY {target} FROM STDIN WITH (FORMAT csv)"))
+        .await
+        .map_err(|e| format!("{e}"))?;
+    if let Err(e) = copy.send(data.as_bytes()).await {
+        return Err(format!("{e}"));
+    }
+    copy.finish().await.map(|_| ()).map_err(|e| format!("{e}"))
+}
+
+/// Insert one batch as a single multi-row `INSERT` inside a transaction.
+async fn insert_batch(
+    pool: &AnyPool,
+    target: &str,
+    batch: &[ImportRow],
+    db_type: DbType,
+) -> Result<(), String> {
+    let values: Vec<String> = batch
+        .iter()
+        .map(|row| {
+            let literals: Vec<String> = row
+                .values
+                .iter()
+                .map(|v| {
+                    v.as_deref()
+                        .map_or("NULL".to_string(), |v| sql_literal(v, db_type))
+                })
+                .collect();
+            format!("({})", literals.join(", "))
+        })
+        .collect();
+
+    let mut tx = pool.begin().await.map_err(|e| format!("{e}"))?;
+    sqlx::query(&format!(
+        "INSERT INTO {target} VALUES {}",
+        values.join(", ")
+    ))
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("{e}"))?;
+    tx.commit().await.map_err(|e| format!("{e}"))
+}
@@ -0,0 +1,174 @@
+//! Durations of the queries run in this session, kept as streaming
+//! histograms so percentiles stay cheap however long the session runs.
+
+/// Exact buckets below this; above it each power of two is split into
+/// this many buckets, so a percentile is off by at most an eighth.
+const SUB_BUCKETS: u64 = 8;
+/// Enough buckets for any `u64` duration.
+const BUCKET_COUNT: usize = ((64 - 2) * SUB_BUCKETS) as usize;
+
+/// Upper bounds in milliseconds of the bins in the distribution chart; the
+/// last bin takes everything above.
+pub const DISTRIBUTION_BOUNDS: [u64; 7] = [1, 5, 10, 50, 100, 500, 1000];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementKind {
+    /// Reads: `SELECT`, `SHOW`, and CTEs that do not modify data.
+    Select,
+    Dml,
+    Ddl,
+    /// Anything else, such as `EXPLAIN`, `SET` or transaction control.
+    Other,
+}
+
+impl StatementKind {
+    pub const ALL: [Self; 4] = [Self::Select, Self::Dml, Self::Ddl, Self::Other];
+
+    /// Classify a statement by its first keyword, skipping leading comments.
+    pub fn of(query: &str) -> Self {
+        let mut rest = query.trim_start();
+        loop {
+            if let Some(after) = rest.strip_prefix("--") {
+                rest = after.split_once('\n').map_or("", |(_, next)| next);
+            } else if let Some(after) = rest.strip_prefix("/*") {
+                rest = after.split_once("*/").map_or("", |(_, next)| next);
+            } else {
+                break;
+            }
+            rest = rest.trim_start();
+        }
+
+        let upper = rest.to_uppercase();
+        let first = upper
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or("");
+        match first {
+            "SELECT" | "TABLE" | "VALUES" | "SHOW" | "DESCRIBE" | "DESC" => Self::Select,
+            "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "REPLACE" | "COPY" | "LOAD" => Self::Dml,
+            "CREATE" | "ALTER" | "DROP" | "TRUNCATE" | "RENAME" | "COMMENT" | "GRANT"
+            | "REVOKE" => Self::Ddl,
+            // A CTE can wrap data-modifying statements
+            "WITH" => {
+                let modifies = upper
+                    .split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .any(|word| matches!(word, "INSERT" | "UPDATE" | "DELETE" | "MERGE"));
+                if modifies { Self::Dml } else { Self::Select }
+            }
+            _ => Self::Other,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Select => "select",
+            Self::Dml => "dml",
+            Self::Ddl => "ddl",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Log-linear histogram of durations in milliseconds.
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    buckets: Vec<u64>,
+    /// Counts for the bins of [`DISTRIBUTION_BOUNDS`], kept exactly.
+    distribution: [u64; DISTRIBUTION_BOUNDS.len() + 1],
+    count: u64,
+    max: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; BUCKET_COUNT],
+            distribution: [0; DISTRIBUTION_BOUNDS.len() + 1],
+            count: 0,
+            max: 0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, ms: u64) {
+        self.buckets[bucket_of(ms)] += 1;
+        let bin = DISTRIBUTION_BOUNDS
+            .iter()
+            .position(|bound| ms < *bound)
+            .unwrap_or(DISTRIBUTION_BOUNDS.len());
+        self.distribution[bin] += 1;
+        self.count += 1;
+        self.max = self.max.max(ms);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Duration that `quantile` (0.0 to 1.0) of the queries finished
+    /// within, or `None` before any query ran.
+    pub fn percentile(&self, quantile: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((quantile * self.count as f64).ceil() as u64).clamp(1, self.count);
+        let mut seen = 0;
+        for (idx, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                // Report the top of the bucket, but never above what was seen
+                return Some(bucket_end(idx).min(self.max));
+            }
+        }
+        Some(self.max)
+    }
+
+    /// Query counts per bin of [`DISTRIBUTION_BOUNDS`].
+    pub fn distribution(&self) -> &[u64] {
+        &self.distribution
+    }
+}
+
+/// Histograms for every query and for each statement kind.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    pub all: LatencyHistogram,
+    by_kind: [LatencyHistogram; StatementKind::ALL.len()],
+}
+
+impl LatencyStats {
+    pub fn record(&mut self, query: &str, ms: u64) {
+        self.all.record(ms);
+        self.by_kind[StatementKind::of(query) as usize].record(ms);
+    }
+
+    pub fn kind(&self, kind: StatementKind) -> &LatencyHistogram {
+        &self.by_kind[kind as usize]
+    }
+}
+
+fn bucket_of(ms: u64) -> usize {
+    if ms < SUB_BUCKETS {
+        return ms as usize;
+    }
+    let exp = 63 - ms.leading_zeros() as u64;
+    let sub = (ms >> (exp - 3)) & (SUB_BUCKETS - 1);
+    ((exp - 2) * SUB_BUCKETS + sub) as usize
+}
+
+/// Largest duration that falls in bucket `idx`.
+fn bucket_end(idx: usize) -> u64 {
+    let idx = idx as u64;
+    if idx < SUB_BUCKETS {
+        return idx;
+    }
+    let exp = idx / SUB_BUCKETS + 2;
+    let sub = idx % SUB_BUCKETS;
+    let start = (SUB_BUCKETS + sub) << (exp - 3);
+    start.saturating_add((1 << (exp - 3)) - 1)
+}
@@ -9,8 +9,19 @@ use tracing_subscriber::prelude::*;
 use tui_logger::{TuiTracingSubscriberLayer, init_logger, set_default_level};
 
 pub mod app;
+pub mod clipboard;
+pub mod ddl;
 pub mod dotline;
+pub mod erd;
 pub mod event;
+pub mod export;
+pub mod finder;
+pub mod import;
+pub mod latency;
+pub mod metrics;
+pub mod monitor;
+pub mod schema_cache;
+pub mod schema_diff;
 pub mod ui;
 
 #[tokio::main]
@@ -30,11 +41,12 @@ async fn main() -> color_eyre::Result<()> {
 
     let cli = Cli::parse();
     let database_url = cli.get_database_url();
+    let graphs = Cli::configured_graphs();
 
     let terminal = ratatui::init();
     execute!(stdout(), EnableBracketedPaste, EnableMouseCapture)?;
 
-    let result = App::new(database_url).run(terminal).await;
+    let result = App::new(database_url, graphs).run(terminal).await;
 
     let _ = execute!(stdout(), DisableMouseCapture, DisableBracketedPaste);
     ratatui::restore();
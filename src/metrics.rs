@@ -0,0 +1,200 @@
+//! Stats pane graphs defined in `config.toml`.
+//!
+//! ```toml
+//! [[graph]]
+//! title = "jobs"
+//! series = [
+//!     { label = "queued", sql = "SELECT count(*) FROM jobs WHERE state = 'queued'" },
+//!     { label = "running", sql = "SELECT count(*) FROM jobs WHERE state = 'running'", interval_secs = 5 },
+//! ]
+//!
+//! [[graph]]
+//! series = [{ metric = "qps" }, { metric = "server_tps" }]
+//! ```
+
+use ratatui::style::Color;
+use sqlx::AnyPool;
+use tracing::warn;
+
+use crate::app::DbType;
+
+/// Colours given to the series of an overlaid graph, in order.
+pub const PALETTE: [Color; 6] = [
+    Color::Rgb(80, 200, 255),
+    Color::Rgb(255, 165, 0),
+    Color::Rgb(200, 120, 255),
+    Color::Rgb(80, 255, 80),
+    Color::Rgb(255, 80, 160),
+    Color::Rgb(255, 255, 0),
+];
+
+/// A series the stats task already samples every second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltinMetric {
+    Qps,
+    Rows,
+    LatencyMs,
+    ServerTps,
+    ServerReads,
+    ServerWrites,
+    AcquireMs,
+    PoolInUse,
+    ServerConnections,
+}
+
+impl BuiltinMetric {
+    /// Short name; MySQL's server rate counts statements, not transactions.
+    pub fn label(self, db_type: DbType) -> &'static str {
+        match self {
+            Self::Qps => "qps",
+            Self::Rows => "rows",
+            Self::LatencyMs => "ms",
+            Self::ServerTps if db_type == DbType::MySQL => "stmt/s",
+            Self::ServerTps => "tps",
+            Self::ServerReads => "read",
+            Self::ServerWrites => "write",
+            Self::AcquireMs => "wait ms",
+            Self::PoolInUse => "pool",
+            Self::ServerConnections => "server conn",
+        }
+    }
+
+    /// Whether the series needs the server-wide counters sampled.
+    pub fn needs_server(self) -> bool {
+        matches!(
+            self,
+            Self::ServerTps | Self::ServerReads | Self::ServerWrites
+        )
+    }
+}
+
+fn default_interval() -> u64 {
+    1
+}
+
+/// One `[[graph]]` entry as written in the config file.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct GraphConfig {
+    pub title: Option<String>,
+    /// Fixed top of the scale, e.g. a queue's capacity.
+    pub limit: Option<u64>,
+    #[serde(default)]
+    pub series: Vec<SeriesConfig>,
+}
+
+/// A series as written in the config file: exactly one of `metric` or `sql`.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct SeriesConfig {
+    pub label: Option<String>,
+    pub metric: Option<BuiltinMetric>,
+    /// Query returning a single number.
+    pub sql: Option<String>,
+    #[serde(default = "default_interval")]
+    pub interval_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeriesSource {
+    Builtin(BuiltinMetric),
+    /// Index into the custom queries polled by the stats task.
+    Query(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    /// Label from the config; otherwise named after the source.
+    pub label: Option<String>,
+    pub source: SeriesSource,
+}
+
+impl Series {
+    pub fn label(&self, db_type: DbType) -> String {
+        match (&self.label, &self.source) {
+            (Some(label), _) => label.clone(),
+            (None, SeriesSource::Builtin(metric)) => metric.label(db_type).to_string(),
+            (None, SeriesSource::Query(_)) => "query".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    pub title: Option<String>,
+    pub limit: Option<u64>,
+    pub series: Vec<Series>,
+}
+
+/// A scalar query polled by the stats task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomQuery {
+    pub sql: String,
+    pub interval_secs: u64,
+}
+
+/// Check the configured graphs and number their queries. Invalid series are
+/// logged and dropped, and so are graphs left without any.
+pub fn resolve(configs: Vec<GraphConfig>) -> (Vec<Graph>, Vec<CustomQuery>) {
+    let mut graphs = Vec::new();
+    let mut queries = Vec::new();
+    for (graph_idx, config) in configs.into_iter().enumerate() {
+        let mut series = Vec::new();
+        for entry in config.series {
+            let source = match (entry.metric, entry.sql) {
+                (Some(metric), None) => SeriesSource::Builtin(metric),
+                (None, Some(sql)) => {
+                    queries.push(CustomQuery {
+                        sql,
+                        interval_secs: entry.interval_secs.max(1),
+                    });
+                    SeriesSource::Query(queries.len() - 1)
+                }
+                _ => {
+                    warn!(
+                        "Graph {}: a series needs exactly one of `metric` or `sql`",
+                        graph_idx + 1
+                    );
+                    continue;
+                }
+            };
+            series.push(Series {
+                label: entry.label,
+                source,
+            });
+        }
+        if series.is_empty() {
+            warn!("Graph {} has no usable series, skipping it", graph_idx + 1);
+            continue;
+        }
+        graphs.push(Graph {
+            title: config.title,
+            limit: config.limit,
+            series,
+        });
+    }
+    (graphs, queries)
+}
+
+/// Run a query that returns one number. Wrapping it as a scalar subquery
+/// lets any numeric type come back as text; the closing parens go on their
+/// own line so a trailing `--` comment can't swallow them. NULL, e.g. the
+/// `max()` of an empty table, reads as 0.
+pub async fn poll_scalar(pool: &AnyPool, db_type: DbType, sql: &str) -> Result<u64, String> {
+    let sql = sql.trim().trim_end_matches(';');
+    let wrapped = match db_type {
+        DbType::Postgres => format!("SELECT (({sql}\n))::text"),
+        DbType::MySQL => format!("SELECT CAST(({sql}\n) AS CHAR)"),
+    };
+    let value: Option<String> = sqlx::query_scalar(&wrapped)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(value) = value else {
+        return Ok(0);
+    };
+    value
+        .trim()
+        .parse::<f64>()
+        .map(|v| v.max(0.0).round() as u64)
+        .map_err(|_| format!("not a number: {value:?}"))
+}
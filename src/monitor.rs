@@ -0,0 +1,777 @@
+//! Server-side activity: what other clients are doing on the database.
+
+use sqlx::any::AnyRow;
+use sqlx::{AnyPool, Row};
+
+use crate::app::{DbType, quote_ident};
+
+/// One server connection: a Postgres backend or a MySQL thread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerSession {
+    pub pid: i64,
+    pub user: String,
+    /// Client address, or `local` for Unix socket connections.
+    pub client: String,
+    pub database: String,
+    pub application: String,
+    pub state: String,
+    /// What the session is blocked on, e.g. `Lock:transactionid`.
+    pub wait_event: String,
+    /// Seconds in the current state; for active sessions, the query's runtime.
+    pub duration_secs: Option<f64>,
+    pub query: String,
+}
+
+impl ServerSession {
+    pub fn is_idle(&self) -> bool {
+        self.state == "idle" || self.state == "Sleep"
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionAction {
+    /// Cancel the running query but keep the connection.
+    Cancel,
+    /// Close the connection.
+    Terminate,
+}
+
+impl SessionAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Cancel => "Cancel query of",
+            Self::Terminate => "Terminate",
+        }
+    }
+}
+
+pub async fn fetch_sessions(pool: &AnyPool, db_type: DbType) -> Result<Vec<ServerSession>, String> {
+    let rows = match db_type {
+        DbType::Postgres => {
+            sqlx::query(
+                r#"SELECT pid::text, COALESCE(usename::text, ''),
+                          CASE WHEN client_port = -1 THEN 'local'
+                               ELSE COALESCE(host(client_addr), '') END,
+                          COALESCE(datname::text, ''), COALESCE(application_name, ''),
+                          COALESCE(state, ''),
+                          COALESCE(wait_event_type || ':' || wait_event, ''),
+                          COALESCE(EXTRACT(EPOCH FROM now() - CASE WHEN state = 'active'
+                                   THEN query_start ELSE state_change END)::float8::text, ''),
+                          COALESCE(query, '')
+                   FROM pg_stat_activity
+                   WHERE backend_type = 'client backend'"#,
+            )
+            .fetch_all(pool)
+            .await
+        }
+        DbType::MySQL => {
+            // Wait events need performance_schema, which may be switched off.
+            // Nested waits are current at once; the newest is the innermost
+            let with_waits = sqlx::query(
+                r#"SELECT CAST(p.id AS CHAR), p.user, p.host, COALESCE(p.db, ''), '',
+                          CONCAT_WS(': ', p.command, NULLIF(p.state, '')),
+                          COALESCE((SELECT w.event_name
+                                    FROM performance_schema.events_waits_current w
+                                    WHERE w.thread_id = t.thread_id AND w.end_event_id IS NULL
+                                    ORDER BY w.event_id DESC
+                                    LIMIT 1), ''),
+                          CAST(p.time AS CHAR), COALESCE(p.info, '')
+                   FROM information_schema.processlist p
+                   LEFT JOIN performance_schema.threads t ON t.processlist_id = p.id
+                   WHERE p.command <> 'Daemon'"#,
+            )
+            .fetch_all(pool)
+            .await;
+            match with_waits {
+                Ok(rows) => Ok(rows),
+                Err(_) => {
+                    sqlx::query(
+                        r#"SELECT CAST(id AS CHAR), user, host, COALESCE(db, ''), '',
+                                  CONCAT_WS(': ', command, NULLIF(state, '')), '',
+                                  CAST(time AS CHAR), COALESCE(info, '')
+                           FROM information_schema.processlist
+                           WHERE command <> 'Daemon'"#,
+                    )
+                    .fetch_all(pool)
+                    .await
+                }
+            }
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .iter()
+        .map(|r| {
+            let get = |i: usize| r.try_get::<String, _>(i).unwrap_or_default();
+            ServerSession {
+                pid: get(0).parse().unwrap_or_default(),
+                user: get(1),
+                client: get(2),
+                database: get(3),
+                application: get(4),
+                state: get(5),
+                wait_event: get(6),
+                duration_secs: get(7).parse().ok(),
+                query: get(8),
+            }
+        })
+        .collect())
+}
+
+/// Cancel or terminate another session.
+pub async fn session_action(
+    pool: &AnyPool,
+    db_type: DbType,
+    pid: i64,
+    action: SessionAction,
+) -> Result<(), String> {
+    match db_type {
+        DbType::Postgres => {
+            let function = match action {
+                SessionAction::Cancel => "pg_cancel_backend",
+                SessionAction::Terminate => "pg_terminate_backend",
+            };
+            let signalled: String = sqlx::query_scalar(&format!("SELECT {function}({pid})::text"))
+                .fetch_one(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            if signalled == "true" {
+                Ok(())
+            } else {
+                Err(format!("backend {pid} not found"))
+            }
+        }
+        DbType::MySQL => {
+            let kill = match action {
+                SessionAction::Cancel => "KILL QUERY",
+                SessionAction::Terminate => "KILL",
+            };
+            sqlx::query(&format!("{kill} {pid}"))
+                .execute(pool)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// One edge of the wait graph: `waiter` wants a lock that `blocker` holds
+/// or is queued ahead for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockWait {
+    pub waiter: i64,
+    pub blocker: i64,
+    /// Mode the waiter asked for, e.g. `AccessExclusiveLock` or `X,REC_NOT_GAP`.
+    pub mode: String,
+    /// Relation, row or transaction being waited on.
+    pub target: String,
+    /// Modes the blocker holds on the target; empty when it is only queued ahead.
+    pub held: String,
+}
+
+pub async fn fetch_lock_waits(pool: &AnyPool, db_type: DbType) -> Result<Vec<LockWait>, String> {
+    let rows = match db_type {
+        DbType::Postgres => {
+            sqlx::query(
+                r#"WITH locks AS (
+                       SELECT pid, mode, granted,
+                              CASE WHEN relation IS NOT NULL THEN relation::regclass::text
+                                   WHEN transactionid IS NOT NULL THEN 'transaction ' || transactionid
+                                   WHEN virtualxid IS NOT NULL THEN 'virtualxid ' || virtualxid
+                                   ELSE locktype END AS target
+                       FROM pg_locks
+                   ), waits AS (
+                       SELECT pid AS waiter, unnest(pg_blocking_pids(pid)) AS blocker, mode, target
+                       FROM locks
+                       WHERE NOT granted
+                   )
+                   SELECT w.waiter::text, w.blocker::text, w.mode, w.target,
+                          COALESCE((SELECT string_agg(DISTINCT h.mode, ', ') FROM locks h
+                                    WHERE h.pid = w.blocker AND h.granted
+                                      AND h.target = w.target), '')
+                   FROM waits w
+                   ORDER BY w.blocker, w.waiter"#,
+            )
+            .fetch_all(pool)
+            .await
+        }
+        DbType::MySQL => {
+            sqlx::query(
+                r#"SELECT CAST(rt.processlist_id AS CHAR), CAST(bt.processlist_id AS CHAR),
+                          rl.lock_mode,
+                          CONCAT_WS('.', rl.object_schema, rl.object_name, rl.index_name),
+                          COALESCE(bl.lock_mode, '')
+                   FROM performance_schema.data_lock_waits w
+                   JOIN performance_schema.data_locks rl
+                       ON rl.engine_lock_id = w.requesting_engine_lock_id
+                   LEFT JOIN performance_schema.data_locks bl
+                       ON bl.engine_lock_id = w.blocking_engine_lock_id
+                          AND bl.lock_status = 'GRANTED'
+                   JOIN performance_schema.threads rt ON rt.thread_id = w.requesting_thread_id
+                   JOIN performance_schema.threads bt ON bt.thread_id = w.blocking_thread_id
+                   ORDER BY bt.processlist_id, rt.processlist_id"#,
+            )
+            .fetch_all(pool)
+            .await
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .iter()
+        .map(|r| {
+            let get = |i: usize| r.try_get::<String, _>(i).unwrap_or_default();
+            LockWait {
+                waiter: get(0).parse().unwrap_or_default(),
+                blocker: get(1).parse().unwrap_or_default(),
+                mode: get(2),
+                target: get(3),
+                held: get(4),
+            }
+        })
+        .collect())
+}
+
+/// A line of the blocking tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockingNode {
+    pub depth: usize,
+    pub pid: i64,
+    /// How this session waits on its parent; `None` for the head of a chain.
+    pub wait: Option<LockWait>,
+}
+
+/// Flatten the wait graph into trees headed by sessions that block others
+/// without waiting themselves, then into trees for any deadlock cycles
+/// those don't reach. A waiter stuck behind several sessions appears under
+/// each of them.
+pub fn blocking_tree(waits: &[LockWait]) -> Vec<BlockingNode> {
+    fn walk(
+        waits: &[LockWait],
+        node: BlockingNode,
+        path: &mut Vec<i64>,
+        out: &mut Vec<BlockingNode>,
+    ) {
+        let (pid, depth) = (node.pid, node.depth);
+        out.push(node);
+        path.push(pid);
+        for wait in waits.iter().filter(|w| w.blocker == pid) {
+            // Deadlocks are cycles; the server breaks them, we just stop
+            if !path.contains(&wait.waiter) {
+                let child = BlockingNode {
+                    depth: depth + 1,
+                    pid: wait.waiter,
+                    wait: Some(wait.clone()),
+                };
+                walk(waits, child, path, out);
+            }
+        }
+        path.pop();
+    }
+
+    let mut heads: Vec<i64> = Vec::new();
+    for pid in waits.iter().map(|w| w.blocker) {
+        if !heads.contains(&pid) && !waits.iter().any(|w| w.waiter == pid) {
+            heads.push(pid);
+        }
+    }
+
+    let mut out = Vec::new();
+    for pid in heads {
+        let head = BlockingNode {
+            depth: 0,
+            pid,
+            wait: None,
+        };
+        walk(waits, head, &mut Vec::new(), &mut out);
+    }
+    // A waiter no head reaches sits on or behind a cycle; climb its blockers
+    // until one repeats and start from there
+    while let Some(mut pid) = waits
+        .iter()
+        .map(|w| w.waiter)
+        .find(|pid| !out.iter().any(|node| node.pid == *pid))
+    {
+        let mut climbed = Vec::new();
+        while !climbed.contains(&pid) {
+            climbed.push(pid);
+            match waits.iter().find(|w| w.waiter == pid) {
+                Some(wait) => pid = wait.blocker,
+                None => break,
+            }
+        }
+        let head = BlockingNode {
+            depth: 0,
+            pid,
+            wait: None,
+        };
+        walk(waits, head, &mut Vec::new(), &mut out);
+    }
+    out
+}
+
+/// Cumulative statistics for one normalised statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryStat {
+    /// Statement text with literals replaced by `$1` or `?`.
+    pub query: String,
+    pub calls: i64,
+    pub total_ms: f64,
+    pub mean_ms: f64,
+    pub rows: i64,
+    /// Share of block reads served from shared buffers; Postgres only.
+    pub hit_ratio: Option<f64>,
+}
+
+/// Statement statistics and how their normalised text can be explained.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryStats {
+    pub stats: Vec<QueryStat>,
+    /// The server can `EXPLAIN (GENERIC_PLAN)` a statement that still has
+    /// its `$n` placeholders; Postgres 16 and later.
+    pub generic_plan: bool,
+}
+
+/// Most expensive statements in the current database, by total time.
+pub async fn fetch_query_stats(pool: &AnyPool, db_type: DbType) -> Result<QueryStats, String> {
+    let mut generic_plan = false;
+    let rows = match db_type {
+        DbType::Postgres => {
+            // The extension can live outside search_path, e.g. in `extensions`
+            let extension = sqlx::query(
+                r#"SELECT e.extversion, n.nspname::text,
+                          current_setting('server_version_num')
+                   FROM pg_extension e
+                   JOIN pg_namespace n ON n.oid = e.extnamespace
+                   WHERE e.extname = 'pg_stat_statements'"#,
+            )
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            let Some(extension) = extension else {
+                return Err("pg_stat_statements is not installed in this database".to_string());
+            };
+            let get = |i: usize| extension.try_get::<String, _>(i).unwrap_or_default();
+            let (version, schema) = (get(0), quote_ident(&get(1), db_type));
+            generic_plan = get(2).parse::<u32>().is_ok_and(|num| num >= 160_000);
+            // 1.8 (Postgres 13) split planning from execution time
+            let numbers: Vec<u32> = version.split('.').filter_map(|n| n.parse().ok()).collect();
+            let (total, mean) = if numbers.as_slice() >= [1, 8].as_slice() {
+                ("total_exec_time", "mean_exec_time")
+            } else {
+                ("total_time", "mean_time")
+            };
+            sqlx::query(&format!(
+                r#"SELECT query, calls::text, {total}::text, {mean}::text, rows::text,
+                          COALESCE((shared_blks_hit::float8
+                              / NULLIF(shared_blks_hit + shared_blks_read, 0))::text, '')
+                   FROM {schema}.pg_stat_statements s
+                   WHERE dbid = (SELECT oid FROM pg_database WHERE datname = current_database())
+                   ORDER BY s.{total} DESC
+                   LIMIT 500"#
+            ))
+            .fetch_all(pool)
+            .await
+        }
+        DbType::MySQL => {
+            // Timers are in picoseconds
+            sqlx::query(
+                r#"SELECT digest_text, CAST(count_star AS CHAR),
+                          CAST(sum_timer_wait / 1e9 AS CHAR), CAST(avg_timer_wait / 1e9 AS CHAR),
+                          CAST(sum_rows_sent + sum_rows_affected AS CHAR), ''
+                   FROM performance_schema.events_statements_summary_by_digest
+                   WHERE digest_text IS NOT NULL
+                     AND (schema_name = DATABASE() OR DATABASE() IS NULL)
+                   ORDER BY sum_timer_wait DESC
+                   LIMIT 500"#,
+            )
+            .fetch_all(pool)
+            .await
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    let stats = rows
+        .iter()
+        .map(|r| {
+            let get = |i: usize| r.try_get::<String, _>(i).unwrap_or_default();
+            QueryStat {
+                query: get(0),
+                calls: get(1).parse().unwrap_or_default(),
+                total_ms: get(2).parse().unwrap_or_default(),
+                mean_ms: get(3).parse().unwrap_or_default(),
+                rows: get(4).parse().unwrap_or_default(),
+                hit_ratio: get(5).parse().ok(),
+            }
+        })
+        .collect();
+    Ok(QueryStats {
+        stats,
+        generic_plan,
+    })
+}
+
+/// Server-wide cumulative counters; rates come from the difference between
+/// two samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServerCounters {
+    /// Committed and rolled back transactions (Postgres) or statements,
+    /// `Questions` (MySQL), whose commit counters miss autocommit work.
+    pub transactions: u64,
+    /// Tuples returned by scans (Postgres) or `Com_select` (MySQL).
+    pub reads: u64,
+    /// Tuples inserted, updated and deleted (Postgres) or the write `Com_*`
+    /// statements (MySQL).
+    pub writes: u64,
+}
+
+impl ServerCounters {
+    /// Per-second rates since `previous`, sampled `secs` apart. Counters
+    /// that went backwards (a stats reset) count as zero.
+    pub fn rates_since(&self, previous: &Self, secs: f64) -> Self {
+        let rate =
+            |now: u64, before: u64| (now.saturating_sub(before) as f64 / secs).round() as u64;
+        Self {
+            transactions: rate(self.transactions, previous.transactions),
+            reads: rate(self.reads, previous.reads),
+            writes: rate(self.writes, previous.writes),
+        }
+    }
+}
+
+pub async fn fetch_server_counters(
+    pool: &AnyPool,
+    db_type: DbType,
+) -> Result<ServerCounters, String> {
+    match db_type {
+        DbType::Postgres => {
+            let row = sqlx::query(
+                r#"SELECT COALESCE(SUM(xact_commit + xact_rollback), 0)::text,
+                          COALESCE(SUM(tup_returned), 0)::text,
+                          COALESCE(SUM(tup_inserted + tup_updated + tup_deleted), 0)::text
+                   FROM pg_stat_database"#,
+            )
+            .fetch_one(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            let get = |i: usize| {
+                row.try_get::<String, _>(i)
+                    .unwrap_or_default()
+                    .parse()
+                    .unwrap_or_default()
+            };
+            Ok(ServerCounters {
+                transactions: get(0),
+                reads: get(1),
+                writes: get(2),
+            })
+        }
+        DbType::MySQL => {
+            let rows = sqlx::query(
+                r#"SHOW GLOBAL STATUS
+                   WHERE Variable_name IN ('Questions', 'Com_select', 'Com_insert',
+                       'Com_insert_select', 'Com_update', 'Com_update_multi', 'Com_delete',
+                       'Com_delete_multi', 'Com_replace', 'Com_replace_select')"#,
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            let mut counters = ServerCounters::default();
+            for row in &rows {
+                let name: String = row.try_get(0).unwrap_or_default();
+                let value: u64 = row
+                    .try_get::<String, _>(1)
+                    .unwrap_or_default()
+                    .parse()
+                    .unwrap_or_default();
+                match name.as_str() {
+                    "Questions" => counters.transactions = value,
+                    "Com_select" => counters.reads = value,
+                    _ => counters.writes += value,
+                }
+            }
+            Ok(counters)
+        }
+    }
+}
+
+/// Server connections in use against the configured ceiling.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionUsage {
+    pub used: u64,
+    pub max: u64,
+}
+
+pub async fn fetch_connection_usage(
+    pool: &AnyPool,
+    db_type: DbType,
+) -> Result<ConnectionUsage, String> {
+    let parse = |value: String| value.parse().unwrap_or_default();
+    match db_type {
+        DbType::Postgres => {
+            let row = sqlx::query(
+                r#"SELECT COUNT(*)::text, current_setting('max_connections')
+                   FROM pg_stat_activity
+                   WHERE backend_type = 'client backend'"#,
+            )
+            .fetch_one(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(ConnectionUsage {
+                used: parse(row.try_get(0).unwrap_or_default()),
+                max: parse(row.try_get(1).unwrap_or_default()),
+            })
+        }
+        DbType::MySQL => {
+            let row = sqlx::query("SHOW GLOBAL STATUS LIKE 'Threads_connected'")
+                .fetch_one(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            let max: String = sqlx::query_scalar("SELECT CAST(@@max_connections AS CHAR)")
+                .fetch_one(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(ConnectionUsage {
+                used: parse(row.try_get(1).unwrap_or_default()),
+                max: parse(max),
+            })
+        }
+    }
+}
+
+/// Maintenance and access statistics for one table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableHealth {
+    pub schema: String,
+    pub table: String,
+    pub live_tuples: i64,
+    /// Rows waiting for vacuum; Postgres only.
+    pub dead_tuples: Option<i64>,
+    /// Seconds since the last manual or automatic vacuum.
+    pub vacuum_age_secs: Option<f64>,
+    /// Seconds since the last manual or automatic analyze.
+    pub analyze_age_secs: Option<f64>,
+    /// Share of scans (Postgres) or row reads (MySQL) that used an index.
+    pub index_usage: Option<f64>,
+}
+
+impl TableHealth {
+    pub fn dead_ratio(&self) -> Option<f64> {
+        let dead = self.dead_tuples?;
+        let total = dead + self.live_tuples;
+        (total > 0).then(|| dead as f64 / total as f64)
+    }
+}
+
+/// A replication link as seen from this server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaLag {
+    /// Standby name on a primary, or the upstream host on a replica.
+    pub name: String,
+    pub state: String,
+    pub lag_secs: Option<f64>,
+    /// WAL not yet replayed by the standby; Postgres primaries only.
+    pub lag_bytes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HealthReport {
+    pub cache_hit_ratio: Option<f64>,
+    pub tables: Vec<TableHealth>,
+    /// Whether the engine records vacuum and analyze times; MySQL has no
+    /// vacuum and keeps no analyze time in the views read here.
+    pub tracks_maintenance: bool,
+    /// Whether this server is itself a replica.
+    pub is_replica: bool,
+    pub replicas: Vec<ReplicaLag>,
+    /// Why replication status could not be read, e.g. missing privileges.
+    pub replication_error: Option<String>,
+}
+
+pub async fn fetch_health(pool: &AnyPool, db_type: DbType) -> Result<HealthReport, String> {
+    let text = |row: &AnyRow, i: usize| row.try_get::<String, _>(i).unwrap_or_default();
+    let mut report = HealthReport {
+        tracks_maintenance: db_type == DbType::Postgres,
+        ..Default::default()
+    };
+
+    match db_type {
+        DbType::Postgres => {
+            let ratio: String = sqlx::query_scalar(
+                r#"SELECT COALESCE((blks_hit::float8 / NULLIF(blks_hit + blks_read, 0))::text, '')
+                   FROM pg_stat_database
+                   WHERE datname = current_database()"#,
+            )
+            .fetch_one(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            report.cache_hit_ratio = ratio.parse().ok();
+
+            let rows = sqlx::query(
+                r#"SELECT schemaname::text, relname::text, n_live_tup::text, n_dead_tup::text,
+                          COALESCE(EXTRACT(EPOCH FROM now()
+                              - GREATEST(last_vacuum, last_autovacuum))::text, ''),
+                          COALESCE(EXTRACT(EPOCH FROM now()
+                              - GREATEST(last_analyze, last_autoanalyze))::text, ''),
+                          COALESCE((idx_scan::float8 / NULLIF(seq_scan + idx_scan, 0))::text, '')
+                   FROM pg_stat_user_tables t
+                   ORDER BY t.n_dead_tup DESC, t.schemaname, t.relname"#,
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            report.tables = rows
+                .iter()
+                .map(|r| TableHealth {
+                    schema: text(r, 0),
+                    table: text(r, 1),
+                    live_tuples: text(r, 2).parse().unwrap_or_default(),
+                    dead_tuples: text(r, 3).parse().ok(),
+                    vacuum_age_secs: text(r, 4).parse().ok(),
+                    analyze_age_secs: text(r, 5).parse().ok(),
+                    index_usage: text(r, 6).parse().ok(),
+                })
+                .collect();
+
+            let replication = async {
+                let is_replica: String = sqlx::query_scalar("SELECT pg_is_in_recovery()::text")
+                    .fetch_one(pool)
+                    .await?;
+                let rows = if is_replica == "true" {
+                    sqlx::query(
+                        r#"SELECT COALESCE(sender_host, ''), COALESCE(status, ''),
+                                  COALESCE(EXTRACT(EPOCH FROM now()
+                                      - pg_last_xact_replay_timestamp())::text, ''), ''
+                           FROM pg_stat_wal_receiver"#,
+                    )
+                    .fetch_all(pool)
+                    .await?
+                } else {
+                    sqlx::query(
+                        r#"SELECT COALESCE(NULLIF(application_name, ''), host(client_addr), ''),
+                                  COALESCE(state, ''),
+                                  COALESCE(EXTRACT(EPOCH FROM replay_lag)::text, ''),
+                                  COALESCE(pg_wal_lsn_diff(pg_current_wal_lsn(), replay_lsn)::text, '')
+                           FROM pg_stat_replication
+                           ORDER BY 1"#,
+                    )
+                    .fetch_all(pool)
+                    .await?
+                };
+                Ok::<_, sqlx::Error>((is_replica == "true", rows))
+            };
+            match replication.await {
+                Ok((is_replica, rows)) => {
+                    report.is_replica = is_replica;
+                    report.replicas = rows
+                        .iter()
+                        .map(|r| ReplicaLag {
+                            name: text(r, 0),
+                            state: text(r, 1),
+                            lag_secs: text(r, 2).parse().ok(),
+                            lag_bytes: text(r, 3).parse::<f64>().ok().map(|b| b as i64),
+                        })
+                        .collect();
+                }
+                Err(e) => report.replication_error = Some(e.to_string()),
+            }
+        }
+        DbType::MySQL => {
+            let rows = sqlx::query(
+                r#"SHOW GLOBAL STATUS
+                   WHERE Variable_name IN ('Innodb_buffer_pool_read_requests',
+                                           'Innodb_buffer_pool_reads')"#,
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            let status = |name: &str| {
+                rows.iter()
+                    .find(|r| text(r, 0) == name)
+                    .and_then(|r| text(r, 1).parse::<f64>().ok())
+            };
+            if let (Some(requests), Some(misses)) = (
+                status("Innodb_buffer_pool_read_requests"),
+                status("Innodb_buffer_pool_reads"),
+            ) && requests > 0.0
+            {
+                report.cache_hit_ratio = Some(1.0 - misses / requests);
+            }
+
+            // Index usage needs performance_schema; tables are listed either way
+            let rows = sqlx::query(
+                r#"SELECT t.table_schema, t.table_name, CAST(COALESCE(t.table_rows, 0) AS CHAR),
+                          COALESCE(CAST(SUM(CASE WHEN io.index_name IS NOT NULL
+                                                 THEN io.count_read END)
+                                        / NULLIF(SUM(io.count_read), 0) AS CHAR), '')
+                   FROM information_schema.tables t
+                   LEFT JOIN performance_schema.table_io_waits_summary_by_index_usage io
+                       ON io.object_schema = t.table_schema AND io.object_name = t.table_name
+                   WHERE t.table_schema = DATABASE() AND t.table_type = 'BASE TABLE'
+                   GROUP BY t.table_schema, t.table_name, t.table_rows
+                   ORDER BY t.table_name"#,
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            report.tables = rows
+                .iter()
+                .map(|r| TableHealth {
+                    schema: text(r, 0),
+                    table: text(r, 1),
+                    live_tuples: text(r, 2).parse().unwrap_or_default(),
+                    dead_tuples: None,
+                    vacuum_age_secs: None,
+                    analyze_age_secs: None,
+                    index_usage: text(r, 3).parse().ok(),
+                })
+                .collect();
+
+            // SHOW REPLICA STATUS arrived in 8.0.22; older servers only
+            // know the SLAVE spelling and column names
+            let (rows, source, io, sql, behind) =
+                match sqlx::query("SHOW REPLICA STATUS").fetch_all(pool).await {
+                    Ok(rows) => (
+                        Ok(rows),
+                        "Source_Host",
+                        "Replica_IO_Running",
+                        "Replica_SQL_Running",
+                        "Seconds_Behind_Source",
+                    ),
+                    Err(_) => (
+                        sqlx::query("SHOW SLAVE STATUS").fetch_all(pool).await,
+                        "Master_Host",
+                        "Slave_IO_Running",
+                        "Slave_SQL_Running",
+                        "Seconds_Behind_Master",
+                    ),
+                };
+            match rows {
+                Ok(rows) => {
+                    report.is_replica = !rows.is_empty();
+                    report.replicas = rows
+                        .iter()
+                        .map(|r| {
+                            let get =
+                                |column: &str| r.try_get::<String, _>(column).unwrap_or_default();
+                            ReplicaLag {
+                                name: get(source),
+                                state: format!("io {} · sql {}", get(io), get(sql)),
+                                lag_secs: r
+                                    .try_get::<Option<i64>, _>(behind)
+                                    .ok()
+                                    .flatten()
+                                    .map(|secs| secs as f64),
+                                lag_bytes: None,
+                            }
+                        })
+                        .collect();
+                }
+                Err(e) => report.replication_error = Some(e.to_string()),
+            }
+        }
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,78 @@
+//! On-disk copy of the last schema seen for each connection.
+//!
+//! Introspecting a large database takes a while, so the previous structure
+//! is shown as soon as the connection is up and swapped out once the live
+//! fetch completes. Files live under `$XDG_CACHE_HOME/lazydb/schema/`,
+//! one JSON file per connection URL (with any password removed).
+
+use crate::event::DatabaseStructure;
+use std::env;
+use std::path::PathBuf;
+
+/// Bumped whenever the cached structure's shape changes, so old files are
+/// ignored instead of failing to parse.
+const CACHE_VERSION: u32 = 2;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CacheFile<S> {
+    version: u32,
+    structure: S,
+}
+
+fn cache_dir() -> PathBuf {
+    env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .ok()
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.cache_dir().to_path_buf()))
+        .unwrap_or_else(env::temp_dir)
+        .join("lazydb")
+        .join("schema")
+}
+
+/// Cache file for a connection URL. Credentials never end up in the name.
+pub fn path(database_url: &str) -> PathBuf {
+    let key = match url::Url::parse(database_url) {
+        Ok(mut url) => {
+            let _ = url.set_password(None);
+            url.set_query(None);
+            url.to_string()
+        }
+        Err(_) => database_url.to_string(),
+    };
+    let name: String = key
+        .trim_end_matches('/')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    cache_dir().join(format!("{name}.json"))
+}
+
+/// The cached structure for a connection, if there is a readable one.
+pub fn load(database_url: &str) -> Option<DatabaseStructure> {
+    let contents = std::fs::read_to_string(path(database_url)).ok()?;
+    let file: CacheFile<DatabaseStructure> = serde_json::from_str(&contents).ok()?;
+    (file.version == CACHE_VERSION).then_some(file.structure)
+}
+
+/// Replace the cached structure for a connection.
+pub fn save(database_url: &str, structure: &DatabaseStructure) -> color_eyre::Result<()> {
+    let path = path(database_url);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = CacheFile {
+        version: CACHE_VERSION,
+        structure,
+    };
+    // Write then rename, so a crash never leaves a truncated cache behind
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(&file)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
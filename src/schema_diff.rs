@@ -0,0 +1,896 @@
+//! Structural diff between two databases and the migration that reconciles
+//! them.
+//!
+//! The *source* is the reference copy (e.g. dev) and the *target* the one
+//! to be brought in line (e.g. prod), so "missing" means present in the
+//! source but not the target. Only tables are compared: their columns
+//! (type and nullability), indexes, foreign keys and check/unique
+//! constraints. Views, routines and column defaults are left alone.
+
+use std::fmt::Write;
+
+use crate::app::{DbType, quote_ident};
+use crate::event::{
+    ConstraintKind, DatabaseStructure, DbColumn, DbConstraint, DbForeignKey, DbIndex, DbSchema,
+    DbTable,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    /// Only in the source; the migration creates it.
+    Missing,
+    /// Only in the target; the migration drops it.
+    Extra,
+    /// In both, but different; the migration alters it.
+    Changed,
+}
+
+impl Change {
+    pub fn sign(self) -> &'static str {
+        match self {
+            Self::Missing => "+",
+            Self::Extra => "-",
+            Self::Changed => "~",
+        }
+    }
+}
+
+/// The object a difference is about. `source` and `target` hold whichever
+/// sides exist.
+#[derive(Debug, Clone)]
+pub enum DiffItem {
+    Schema,
+    /// The table as it exists on whichever side has it.
+    Table(Box<DbTable>),
+    Column {
+        source: Option<DbColumn>,
+        target: Option<DbColumn>,
+    },
+    Index {
+        source: Option<DbIndex>,
+        target: Option<DbIndex>,
+    },
+    ForeignKey {
+        source: Option<DbForeignKey>,
+        target: Option<DbForeignKey>,
+    },
+    Constraint {
+        source: Option<DbConstraint>,
+        target: Option<DbConstraint>,
+    },
+}
+
+/// One difference, located by the target's schema and table names.
+#[derive(Debug, Clone)]
+pub struct DiffEntry {
+    pub schema: String,
+    pub table: Option<String>,
+    pub change: Change,
+    pub item: DiffItem,
+}
+
+impl DiffEntry {
+    /// One-line summary, e.g. `column age: integer → bigint`.
+    pub fn describe(&self) -> String {
+        let what = match self.change {
+            Change::Missing => "missing",
+            Change::Extra => "extra",
+            Change::Changed => "changed",
+        };
+        match &self.item {
+            DiffItem::Schema => format!("schema {} {what}", self.schema),
+            DiffItem::Table(table) => {
+                let n = table.columns.len();
+                let plural = if n == 1 { "" } else { "s" };
+                format!("table {} {what} ({n} column{plural})", table.name)
+            }
+            DiffItem::Column { source, target } => match (source, target) {
+                (Some(s), Some(t)) => {
+                    let mut parts = Vec::new();
+                    if column_type(s) != column_type(t) {
+                        parts.push(format!("{} → {}", column_type(t), column_type(s)));
+                    }
+                    if s.is_nullable != t.is_nullable {
+                        parts.push(format!("{} → {}", nullability(t), nullability(s)));
+                    }
+                    format!("column {}: {}", s.name, parts.join(", "))
+                }
+                (Some(c), None) | (None, Some(c)) => format!(
+                    "column {} {} {} {what}",
+                    c.name,
+                    column_type(c),
+                    nullability(c)
+                ),
+                (None, None) => String::new(),
+            },
+            DiffItem::Index { source, target } => match (source, target) {
+                (Some(s), Some(t)) => format!(
+                    "index {}: {} → {}",
+                    s.name,
+                    index_summary(t),
+                    index_summary(s)
+                ),
+                (Some(i), None) | (None, Some(i)) => {
+                    format!("index {} {} {what}", i.name, index_summary(i))
+                }
+                (None, None) => String::new(),
+            },
+            DiffItem::ForeignKey { source, target } => match (source, target) {
+                (Some(s), Some(t)) => format!(
+                    "foreign key {}: {} → {}",
+                    s.name,
+                    fk_summary(t),
+                    fk_summary(s)
+                ),
+                (Some(fk), None) | (None, Some(fk)) => {
+                    format!("foreign key {} {} {what}", fk.name, fk_summary(fk))
+                }
+                (None, None) => String::new(),
+            },
+            DiffItem::Constraint { source, target } => match (source, target) {
+                (Some(s), Some(t)) => {
+                    format!("constraint {}: {} → {}", s.name, t.definition, s.definition)
+                }
+                (Some(c), None) | (None, Some(c)) => {
+                    format!("constraint {} {} {what}", c.name, c.definition)
+                }
+                (None, None) => String::new(),
+            },
+        }
+    }
+}
+
+/// Every difference between `source` and `target`, grouped by schema and
+/// table in source order.
+///
+/// When each side has exactly one schema they are compared even if their
+/// names differ, since MySQL databases are often named per environment
+/// (`app_dev`, `app_prod`).
+pub fn diff(source: &DatabaseStructure, target: &DatabaseStructure) -> Vec<DiffEntry> {
+    let mut out = Vec::new();
+
+    if let ([s], [t]) = (source.schemas.as_slice(), target.schemas.as_slice()) {
+        diff_schema(s, t, &mut out);
+        return out;
+    }
+
+    for s in &source.schemas {
+        match target.schemas.iter().find(|t| t.name == s.name) {
+            Some(t) => diff_schema(s, t, &mut out),
+            None => {
+                out.push(DiffEntry {
+                    schema: s.name.clone(),
+                    table: None,
+                    change: Change::Missing,
+                    item: DiffItem::Schema,
+                });
+                for table in &s.tables {
+                    out.push(table_entry(&s.name, table, Change::Missing));
+                }
+            }
+        }
+    }
+    for t in &target.schemas {
+        if !source.schemas.iter().any(|s| s.name == t.name) {
+            out.push(DiffEntry {
+                schema: t.name.clone(),
+                table: None,
+                change: Change::Extra,
+                item: DiffItem::Schema,
+            });
+        }
+    }
+    out
+}
+
+fn table_entry(schema: &str, table: &DbTable, change: Change) -> DiffEntry {
+    DiffEntry {
+        schema: schema.to_string(),
+        table: Some(table.name.clone()),
+        change,
+        item: DiffItem::Table(Box::new(table.clone())),
+    }
+}
+
+fn diff_schema(source: &DbSchema, target: &DbSchema, out: &mut Vec<DiffEntry>) {
+    for s in &source.tables {
+        match target.tables.iter().find(|t| t.name == s.name) {
+            Some(t) => diff_table(&source.name, s, &target.name, t, out),
+            None => {
+                let table = DbTable {
+                    foreign_keys: retarget(&s.foreign_keys, &source.name, &target.name),
+                    ..s.clone()
+                };
+                out.push(table_entry(&target.name, &table, Change::Missing));
+            }
+        }
+    }
+    for t in &target.tables {
+        if !source.tables.iter().any(|s| s.name == t.name) {
+            out.push(table_entry(&target.name, t, Change::Extra));
+        }
+    }
+}
+
+fn diff_table(
+    source_schema: &str,
+    source: &DbTable,
+    schema: &str,
+    target: &DbTable,
+    out: &mut Vec<DiffEntry>,
+) {
+    let mut push = |change, item| {
+        out.push(DiffEntry {
+            schema: schema.to_string(),
+            table: Some(target.name.clone()),
+            change,
+            item,
+        })
+    };
+
+    for (change, source, target) in pair_by_name(&source.columns, &target.columns, |c| &c.name) {
+        if change == Change::Changed
+            && let (Some(s), Some(t)) = (&source, &target)
+            && column_type(s) == column_type(t)
+            && s.is_nullable == t.is_nullable
+        {
+            continue;
+        }
+        push(change, DiffItem::Column { source, target });
+    }
+
+    // Unique constraints are backed by an index of the same name, which is
+    // diffed (and recreated) through the constraint instead
+    let unique_names: Vec<&str> = source
+        .constraints
+        .iter()
+        .chain(&target.constraints)
+        .filter(|c| c.kind == ConstraintKind::Unique)
+        .map(|c| c.name.as_str())
+        .collect();
+    let indexes = |t: &DbTable| -> Vec<DbIndex> {
+        t.indexes
+            .iter()
+            .filter(|i| !unique_names.contains(&i.name.as_str()))
+            .cloned()
+            .collect()
+    };
+    for (change, source, target) in pair_by_name(&indexes(source), &indexes(target), |i| &i.name) {
+        if change == Change::Changed && source == target {
+            continue;
+        }
+        push(change, DiffItem::Index { source, target });
+    }
+
+    let source_fks = retarget(&source.foreign_keys, source_schema, schema);
+    for (change, source, target) in pair_by_name(&source_fks, &target.foreign_keys, |fk| &fk.name) {
+        if change == Change::Changed && source == target {
+            continue;
+        }
+        push(change, DiffItem::ForeignKey { source, target });
+    }
+
+    for (change, source, target) in
+        pair_by_name(&source.constraints, &target.constraints, |c| &c.name)
+    {
+        if change == Change::Changed && source == target {
+            continue;
+        }
+        push(change, DiffItem::Constraint { source, target });
+    }
+}
+
+/// Point references back into the source schema at the target schema, for
+/// when the two are compared under different names.
+fn retarget(fks: &[DbForeignKey], source_schema: &str, schema: &str) -> Vec<DbForeignKey> {
+    fks.iter()
+        .map(|fk| {
+            let mut fk = fk.clone();
+            if fk.ref_schema == source_schema {
+                fk.ref_schema = schema.to_string();
+            }
+            fk
+        })
+        .collect()
+}
+
+/// Match items by name: source order first, then target-only items.
+/// Matched pairs come back as `Changed` and are filtered by the caller.
+fn pair_by_name<T: Clone>(
+    source: &[T],
+    target: &[T],
+    name: impl Fn(&T) -> &String,
+) -> Vec<(Change, Option<T>, Option<T>)> {
+    let mut out: Vec<_> = source
+        .iter()
+        .map(|s| match target.iter().find(|t| name(t) == name(s)) {
+            Some(t) => (Change::Changed, Some(s.clone()), Some(t.clone())),
+            None => (Change::Missing, Some(s.clone()), None),
+        })
+        .collect();
+    out.extend(
+        target
+            .iter()
+            .filter(|t| !source.iter().any(|s| name(s) == name(t)))
+            .map(|t| (Change::Extra, None, Some(t.clone()))),
+    );
+    out
+}
+
+fn column_type(column: &DbColumn) -> &str {
+    if column.column_type.is_empty() {
+        &column.data_type
+    } else {
+        &column.column_type
+    }
+}
+
+fn nullability(column: &DbColumn) -> &'static str {
+    if column.is_nullable {
+        "NULL"
+    } else {
+        "NOT NULL"
+    }
+}
+
+fn index_summary(index: &DbIndex) -> String {
+    let mut s = format!("({})", index.columns.join(", "));
+    if index.is_primary {
+        s.push_str(" primary");
+    } else if index.is_unique {
+        s.push_str(" unique");
+    }
+    if !index.method.is_empty() && index.method != "btree" {
+        let _ = write!(s, " {}", index.method);
+    }
+    s
+}
+
+fn fk_summary(fk: &DbForeignKey) -> String {
+    format!(
+        "({}) → {}.{} ({})",
+        fk.columns.join(", "),
+        fk.ref_schema,
+        fk.ref_table,
+        fk.ref_columns.join(", ")
+    )
+}
+
+/// Order in which statements run, so that dependencies exist before they
+/// are referenced and are dropped only after whatever references them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Phase {
+    DropForeignKeys,
+    DropIndexes,
+    DropTables,
+    DropColumns,
+    CreateSchemas,
+    CreateTables,
+    AlterColumns,
+    CreateIndexes,
+    AddForeignKeys,
+}
+
+/// SQL statements for a single entry, in execution order.
+pub fn entry_sql(entry: &DiffEntry, db_type: DbType) -> Vec<String> {
+    let mut statements = statements(entry, db_type);
+    statements.sort_by_key(|(phase, _)| *phase);
+    statements.into_iter().map(|(_, sql)| sql).collect()
+}
+
+/// A script applying every entry to the target. Statements are ordered so
+/// foreign keys are dropped first and added last.
+pub fn migration_sql(
+    entries: &[DiffEntry],
+    db_type: DbType,
+    source_name: &str,
+    target_name: &str,
+) -> String {
+    let mut statements: Vec<(Phase, String)> = entries
+        .iter()
+        .flat_map(|e| statements(e, db_type))
+        .collect();
+    statements.sort_by_key(|(phase, _)| *phase);
+
+    let mut out = format!(
+        "-- Migration bringing {target_name} in line with {source_name}\n\
+         -- Generated by lazydb. Column defaults, views and routines are not\n\
+         -- compared; review before running.\n\n"
+    );
+    for (_, sql) in statements {
+        out.push_str(&sql);
+        out.push('\n');
+    }
+    out
+}
+
+fn statements(entry: &DiffEntry, db_type: DbType) -> Vec<(Phase, String)> {
+    let q = |name: &str| quote_ident(name, db_type);
+    let schema = q(&entry.schema);
+    let table = entry
+        .table
+        .as_deref()
+        .map(|t| format!("{schema}.{}", q(t)))
+        .unwrap_or_default();
+    let alter = format!("ALTER TABLE {table}");
+
+    match (&entry.item, entry.change) {
+        (DiffItem::Schema, Change::Missing) => {
+            vec![(Phase::CreateSchemas, format!("CREATE SCHEMA {schema};"))]
+        }
+        // Too destructive to generate; the entry is listed for review only
+        (DiffItem::Schema, _) => vec![(
+            Phase::DropTables,
+            format!("-- schema {schema} exists only in the target"),
+        )],
+        (DiffItem::Table(t), Change::Missing) => {
+            let mut lines: Vec<String> = t
+                .columns
+                .iter()
+                .map(|c| format!("    {}", column_definition(c, db_type)))
+                .collect();
+            let pk: Vec<String> = t
+                .columns
+                .iter()
+                .filter(|c| c.is_primary_key)
+                .map(|c| q(&c.name))
+                .collect();
+            if !pk.is_empty() {
+                lines.push(format!("    PRIMARY KEY ({})", pk.join(", ")));
+            }
+            let mut out = vec![(
+                Phase::CreateTables,
+                format!("CREATE TABLE {table} (\n{}\n);", lines.join(",\n")),
+            )];
+            for index in t.indexes.iter().filter(|i| !i.is_primary) {
+                if !t.constraints.iter().any(|c| c.name == index.name) {
+                    out.push((Phase::CreateIndexes, create_index(index, &table, db_type)));
+                }
+            }
+            for c in &t.constraints {
+                out.push((Phase::CreateIndexes, add_constraint(c, &alter, db_type)));
+            }
+            for fk in &t.foreign_keys {
+                out.push((Phase::AddForeignKeys, add_foreign_key(fk, &alter, db_type)));
+            }
+            out
+        }
+        (DiffItem::Table(_), _) => vec![(Phase::DropTables, format!("DROP TABLE {table};"))],
+
+        (DiffItem::Column { source, target }, change) => match (source, target, change) {
+            (Some(c), _, Change::Missing) => vec![(
+                Phase::AlterColumns,
+                format!("{alter} ADD COLUMN {};", column_definition(c, db_type)),
+            )],
+            (_, Some(c), Change::Extra) => vec![(
+                Phase::DropColumns,
+                format!("{alter} DROP COLUMN {};", q(&c.name)),
+            )],
+            (Some(s), Some(t), _) => alter_column(s, t, &alter, db_type)
+                .into_iter()
+                .map(|sql| (Phase::AlterColumns, sql))
+                .collect(),
+            _ => Vec::new(),
+        },
+
+        (DiffItem::Index { source, target }, _) => {
+            let mut out = Vec::new();
+            if let Some(index) = target {
+                out.push((
+                    Phase::DropIndexes,
+                    drop_index(index, &entry.schema, &alter, db_type),
+                ));
+            }
+            if let Some(index) = source {
+                let sql = if index.is_primary {
+                    format!(
+                        "{alter} ADD PRIMARY KEY ({});",
+                        index_columns(index, db_type)
+                    )
+                } else {
+                    create_index(index, &table, db_type)
+                };
+                out.push((Phase::CreateIndexes, sql));
+            }
+            out
+        }
+
+        (DiffItem::ForeignKey { source, target }, _) => {
+            let mut out = Vec::new();
+            if let Some(fk) = target {
+                let drop = match db_type {
+                    DbType::Postgres => "DROP CONSTRAINT",
+                    DbType::MySQL => "DROP FOREIGN KEY",
+                };
+                out.push((
+                    Phase::DropForeignKeys,
+                    format!("{alter} {drop} {};", q(&fk.name)),
+                ));
+            }
+            if let Some(fk) = source {
+                out.push((Phase::AddForeignKeys, add_foreign_key(fk, &alter, db_type)));
+            }
+            out
+        }
+
+        (DiffItem::Constraint { source, target }, _) => {
+            let mut out = Vec::new();
+            if let Some(c) = target {
+                let drop = match (db_type, c.kind) {
+                    (DbType::Postgres, _) => "DROP CONSTRAINT",
+                    (DbType::MySQL, ConstraintKind::Check) => "DROP CHECK",
+                    (DbType::MySQL, ConstraintKind::Unique) => "DROP INDEX",
+                };
+                out.push((
+                    Phase::DropIndexes,
+                    format!("{alter} {drop} {};", q(&c.name)),
+                ));
+            }
+            if let Some(c) = source {
+                out.push((Phase::CreateIndexes, add_constraint(c, &alter, db_type)));
+            }
+            out
+        }
+    }
+}
+
+fn column_definition(column: &DbColumn, db_type: DbType) -> String {
+    let mut def = format!(
+        "{} {}",
+        quote_ident(&column.name, db_type),
+        column_type(column)
+    );
+    if !column.is_nullable {
+        def.push_str(" NOT NULL");
+    }
+    def
+}
+
+fn alter_column(source: &DbColumn, target: &DbColumn, alter: &str, db_type: DbType) -> Vec<String> {
+    let name = quote_ident(&source.name, db_type);
+    match db_type {
+        // MySQL restates the whole column, and attributes we don't read
+        // (AUTO_INCREMENT, DEFAULT, COMMENT, charset) would be dropped, so
+        // the statement is left for the user to complete
+        DbType::MySQL => vec![format!(
+            "-- Review: MODIFY COLUMN replaces the whole definition; add back any\n\
+             -- AUTO_INCREMENT, DEFAULT, COMMENT or CHARACTER SET {name} has, then uncomment.\n\
+             -- {alter} MODIFY COLUMN {}{};",
+            column_definition(source, db_type),
+            if source.is_nullable { " NULL" } else { "" }
+        )],
+        DbType::Postgres => {
+            let mut out = Vec::new();
+            if column_type(source) != column_type(target) {
+                out.push(format!(
+                    "{alter} ALTER COLUMN {name} TYPE {};",
+                    column_type(source)
+                ));
+            }
+            if source.is_nullable != target.is_nullable {
+                let action = if source.is_nullable { "DROP" } else { "SET" };
+                out.push(format!("{alter} ALTER COLUMN {name} {action} NOT NULL;"));
+            }
+            out
+        }
+    }
+}
+
+fn index_columns(index: &DbIndex, db_type: DbType) -> String {
+    match db_type {
+        // pg_get_indexdef already quotes where needed
+        DbType::Postgres => index.columns.join(", "),
+        DbType::MySQL => index
+            .columns
+            .iter()
+            .map(|c| quote_ident(c, db_type))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+fn create_index(index: &DbIndex, table: &str, db_type: DbType) -> String {
+    let name = quote_ident(&index.name, db_type);
+    let columns = index_columns(index, db_type);
+    match db_type {
+        DbType::Postgres => {
+            let unique = if index.is_unique { "UNIQUE " } else { "" };
+            let using = if index.method.is_empty() || index.method == "btree" {
+                String::new()
+            } else {
+                format!(" USING {}", index.method)
+            };
+            format!("CREATE {unique}INDEX {name} ON {table}{using} ({columns});")
+        }
+        DbType::MySQL => {
+            let kind = match index.method.as_str() {
+                "fulltext" => "FULLTEXT ",
+                "spatial" => "SPATIAL ",
+                _ if index.is_unique => "UNIQUE ",
+                _ => "",
+            };
+            format!("CREATE {kind}INDEX {name} ON {table} ({columns});")
+        }
+    }
+}
+
+fn drop_index(index: &DbIndex, schema: &str, alter: &str, db_type: DbType) -> String {
+    let name = quote_ident(&index.name, db_type);
+    match (db_type, index.is_primary) {
+        // The primary key index belongs to its constraint, which shares its name
+        (DbType::Postgres, true) => format!("{alter} DROP CONSTRAINT {name};"),
+        (DbType::Postgres, false) => {
+            format!("DROP INDEX {}.{name};", quote_ident(schema, db_type))
+        }
+        (DbType::MySQL, true) => format!("{alter} DROP PRIMARY KEY;"),
+        (DbType::MySQL, false) => format!("{alter} DROP INDEX {name};"),
+    }
+}
+
+fn add_constraint(constraint: &DbConstraint, alter: &str, db_type: DbType) -> String {
+    format!(
+        "{alter} ADD CONSTRAINT {} {};",
+        quote_ident(&constraint.name, db_type),
+        constraint.definition
+    )
+}
+
+fn add_foreign_key(fk: &DbForeignKey, alter: &str, db_type: DbType) -> String {
+    let list = |names: &[String]| {
+        names
+            .iter()
+            .map(|n| quote_ident(n, db_type))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut sql = format!(
+        "{alter} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}.{} ({})",
+        quote_ident(&fk.name, db_type),
+        list(&fk.columns),
+        quote_ident(&fk.ref_schema, db_type),
+        quote_ident(&fk.ref_table, db_type),
+        list(&fk.ref_columns)
+    );
+    for (action, rule) in [("UPDATE", &fk.on_update), ("DELETE", &fk.on_delete)] {
+        if !rule.is_empty() && rule != "NO ACTION" {
+            let _ = write!(sql, " ON {action} {rule}");
+        }
+    }
+    sql.push(';');
+    sql
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str, is_primary_key: bool) -> DbColumn {
+        DbColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            column_type: String::new(),
+            is_nullable: !is_primary_key,
+            is_primary_key,
+            ordinal_position: 0,
+            comment: None,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<DbColumn>) -> DbTable {
+        DbTable {
+            name: name.to_string(),
+            columns,
+            indexes: Vec::new(),
+            foreign_keys: Vec::new(),
+            constraints: Vec::new(),
+            stats: None,
+            comment: None,
+        }
+    }
+
+    fn foreign_key(name: &str, ref_schema: &str, ref_table: &str) -> DbForeignKey {
+        DbForeignKey {
+            name: name.to_string(),
+            columns: vec!["customer_id".to_string()],
+            ref_schema: ref_schema.to_string(),
+            ref_table: ref_table.to_string(),
+            ref_columns: vec!["id".to_string()],
+            on_update: "NO ACTION".to_string(),
+            on_delete: "CASCADE".to_string(),
+        }
+    }
+
+    fn database(schemas: Vec<(&str, Vec<DbTable>)>) -> DatabaseStructure {
+        DatabaseStructure {
+            schemas: schemas
+                .into_iter()
+                .map(|(name, tables)| DbSchema {
+                    name: name.to_string(),
+                    tables,
+                    views: Vec::new(),
+                    materialized_views: Vec::new(),
+                    routines: Vec::new(),
+                    sequences: Vec::new(),
+                    types: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Line number of the first statement containing `needle`.
+    fn position(script: &str, needle: &str) -> usize {
+        script
+            .lines()
+            .position(|line| line.contains(needle))
+            .unwrap_or_else(|| panic!("{needle:?} not in:\n{script}"))
+    }
+
+    #[test]
+    fn foreign_keys_are_dropped_first_and_added_last() {
+        let customers = table("customers", vec![column("id", "integer", true)]);
+        let mut orders = table(
+            "orders",
+            vec![
+                column("id", "integer", true),
+                column("customer_id", "integer", false),
+            ],
+        );
+        orders.foreign_keys = vec![foreign_key("orders_customer_fk", "public", "customers")];
+        let mut old_orders = orders.clone();
+        old_orders.foreign_keys[0].on_delete = "RESTRICT".to_string();
+        let legacy = table("legacy", vec![column("id", "integer", true)]);
+
+        let source = database(vec![("public", vec![customers, orders])]);
+        let target = database(vec![("public", vec![old_orders, legacy])]);
+        let script = migration_sql(&diff(&source, &target), DbType::Postgres, "dev", "prod");
+
+        let drop_fk = position(&script, "DROP CONSTRAINT \"orders_customer_fk\"");
+        let drop_table = position(&script, "DROP TABLE \"public\".\"legacy\"");
+        let create_table = position(&script, "CREATE TABLE \"public\".\"customers\"");
+        let add_fk = position(&script, "FOREIGN KEY");
+        assert!(drop_fk < drop_table);
+        assert!(drop_table < create_table);
+        assert!(create_table < add_fk);
+        assert!(script[script.find("FOREIGN KEY").unwrap()..].contains("ON DELETE CASCADE;"));
+    }
+
+    #[test]
+    fn primary_key_and_identifiers_are_quoted() {
+        let accounts = table(
+            "Accounts",
+            vec![
+                column("id", "integer", true),
+                column("Org Id", "integer", true),
+                column("name", "text", false),
+            ],
+        );
+        let source = database(vec![("public", vec![accounts])]);
+        let target = database(vec![("public", Vec::new())]);
+        let entries = diff(&source, &target);
+
+        let postgres = migration_sql(&entries, DbType::Postgres, "dev", "prod");
+        assert!(postgres.contains("CREATE TABLE \"public\".\"Accounts\" ("));
+        assert!(postgres.contains("    \"Org Id\" integer NOT NULL,"));
+        assert!(postgres.contains("    PRIMARY KEY (\"id\", \"Org Id\")"));
+
+        let mysql = migration_sql(&entries, DbType::MySQL, "dev", "prod");
+        assert!(mysql.contains("CREATE TABLE `public`.`Accounts` ("));
+        assert!(mysql.contains("    PRIMARY KEY (`id`, `Org Id`)"));
+    }
+
+    #[test]
+    fn mysql_primary_key_columns_are_quoted_when_added() {
+        let mut source_table = table("events", vec![column("order", "int", true)]);
+        source_table.indexes = vec![DbIndex {
+            name: "PRIMARY".to_string(),
+            columns: vec!["order".to_string()],
+            is_unique: true,
+            is_primary: true,
+            method: "btree".to_string(),
+        }];
+        let target_table = table("events", vec![column("order", "int", true)]);
+        let source = database(vec![("app", vec![source_table])]);
+        let target = database(vec![("app", vec![target_table])]);
+
+        let script = migration_sql(&diff(&source, &target), DbType::MySQL, "dev", "prod");
+        assert!(script.contains("ALTER TABLE `app`.`events` ADD PRIMARY KEY (`order`);"));
+    }
+
+    #[test]
+    fn single_schemas_are_compared_across_names() {
+        let users = table("users", vec![column("id", "bigint", true)]);
+        let source = database(vec![("app_dev", vec![users.clone()])]);
+        let target = database(vec![("app_prod", vec![users.clone()])]);
+        assert!(diff(&source, &target).is_empty());
+
+        // With several schemas on a side, only matching names are compared
+        let source = database(vec![
+            ("app_dev", vec![users.clone()]),
+            ("audit", Vec::new()),
+        ]);
+        let target = database(vec![("app_prod", vec![users]), ("audit", Vec::new())]);
+        let schemas: Vec<(String, Change)> = diff(&source, &target)
+            .into_iter()
+            .filter(|e| matches!(e.item, DiffItem::Schema))
+            .map(|e| (e.schema, e.change))
+            .collect();
+        assert_eq!(
+            schemas,
+            vec![
+                ("app_dev".to_string(), Change::Missing),
+                ("app_prod".to_string(), Change::Extra),
+            ]
+        );
+    }
+
+    #[test]
+    fn references_into_the_source_schema_are_retargeted() {
+        let customers = table("customers", vec![column("id", "int", true)]);
+        let mut orders = table(
+            "orders",
+            vec![
+                column("id", "int", true),
+                column("customer_id", "int", false),
+            ],
+        );
+        orders.foreign_keys = vec![foreign_key("orders_customer_fk", "app_dev", "customers")];
+        let mut invoices = orders.clone();
+        invoices.name = "invoices".to_string();
+        invoices.foreign_keys[0].name = "invoices_customer_fk".to_string();
+        let mut prod_orders = orders.clone();
+        prod_orders.foreign_keys[0].ref_schema = "app_prod".to_string();
+
+        let source = database(vec![("app_dev", vec![customers.clone(), orders, invoices])]);
+        let target = database(vec![("app_prod", vec![customers, prod_orders])]);
+        let entries = diff(&source, &target);
+
+        // The existing key points at the same table once retargeted
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].schema, "app_prod");
+        assert_eq!(entries[0].table.as_deref(), Some("invoices"));
+        let script = migration_sql(&entries, DbType::MySQL, "app_dev", "app_prod");
+        assert!(script.contains("REFERENCES `app_prod`.`customers` (`id`)"));
+        assert!(!script.contains("app_dev`"));
+    }
+
+    #[test]
+    fn unique_constraints_are_not_repeated_as_indexes() {
+        let mut users = table(
+            "users",
+            vec![column("id", "int", true), column("email", "text", false)],
+        );
+        users.constraints = vec![DbConstraint {
+            name: "users_email_key".to_string(),
+            kind: ConstraintKind::Unique,
+            definition: "UNIQUE (email)".to_string(),
+        }];
+        users.indexes = vec![DbIndex {
+            name: "users_email_key".to_string(),
+            columns: vec!["email".to_string()],
+            is_unique: true,
+            is_primary: false,
+            method: "btree".to_string(),
+        }];
+        let bare = table(
+            "users",
+            vec![column("id", "int", true), column("email", "text", false)],
+        );
+
+        // Added to an existing table: one constraint entry, no index entry
+        let source = database(vec![("public", vec![users.clone()])]);
+        let target = database(vec![("public", vec![bare])]);
+        let entries = diff(&source, &target);
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(entries[0].item, DiffItem::Constraint { .. }));
+        let script = migration_sql(&entries, DbType::Postgres, "dev", "prod");
+        assert_eq!(script.matches("users_email_key").count(), 1);
+        assert!(!script.contains("CREATE UNIQUE INDEX"));
+
+        // Created with its table: the constraint alone builds the index
+        let source = database(vec![("public", vec![users])]);
+        let target = database(vec![("public", Vec::new())]);
+        let script = migration_sql(&diff(&source, &target), DbType::Postgres, "dev", "prod");
+        assert!(script.contains("ADD CONSTRAINT \"users_email_key\" UNIQUE (email);"));
+        assert!(!script.contains("CREATE UNIQUE INDEX"));
+    }
+}
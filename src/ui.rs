@@ -3,15 +3,27 @@ use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, BorderType, Cell, Clear, Paragraph, Row, Table, Widget, Wrap},
+    widgets::{
+        Bar, BarChart, BarGroup, Block, BorderType, Cell, Clear, Paragraph, Row, Table, Widget,
+        Wrap,
+    },
 };
 use tui_logger::TuiLoggerSmartWidget;
 
 use crate::app::{
-    App, ConnectionManagerMode, ConnectionState, CurrentView, FocusedPane, QueryResultState,
-    TableViewState, TreeNodeId,
+    App, ConnectionManagerMode, ConnectionState, CurrentView, DbType, DefinitionViewState,
+    ErdViewState, FinderState, FocusedPane, HealthViewState, ImportModalState, ImportStage,
+    LocksViewState, QueryResultState, QuerySort, ReferencesModalState, RowDetailState,
+    SchemaDiffViewState, SessionsViewState, TableSort, TableViewState, TopQueriesViewState,
+    TreeGroup, TreeItem, TreeNodeId,
 };
 use crate::dotline::{AsciiDotGraph, make_color_fn};
+use crate::erd::CellKind;
+use crate::event::{ConstraintKind, RoutineKind};
+use crate::finder::CandidateKind;
+use crate::latency::{DISTRIBUTION_BOUNDS, StatementKind};
+use crate::metrics::{BuiltinMetric, Graph, PALETTE, SeriesSource};
+use crate::schema_diff::{self, Change};
 
 const BG: Color = Color::Rgb(20, 20, 20);
 const BORDER_NORMAL: Color = Color::White;
@@ -27,6 +39,7 @@ const ICON_GRAY: Color = Color::Rgb(180, 180, 180);
 const PK_COLOR: Color = Color::Rgb(255, 200, 100);
 const NUMBER_COLOR: Color = Color::Rgb(255, 180, 100);
 const CURSOR_LINE_BG: Color = Color::Rgb(40, 40, 40);
+const MATCH_COLOR: Color = Color::Rgb(255, 220, 120);
 
 const SQL_KEYWORDS: &[&str] = &[
     "SELECT",
@@ -143,8 +156,37 @@ impl Widget for &App {
         }
 
         // Render export modal on top if visible
-        if self.export_modal {
-            render_export_modal(area, buf);
+        if self.export_modal.visible {
+            render_export_modal(self, area, buf);
+        }
+
+        // Render import modal on top if visible
+        if self.import_modal.visible {
+            render_import_modal(&self.import_modal, area, buf);
+        }
+
+        // Render copy modal on top if visible
+        if self.copy_modal {
+            render_copy_modal(area, buf);
+        }
+
+        if self.pending_editor_text.is_some() {
+            render_editor_replace_modal(area, buf);
+        }
+
+        // Render references modal on top if visible
+        if self.references_modal.visible {
+            render_references_modal(&self.references_modal, area, buf);
+        }
+
+        // Render row detail modal on top if visible
+        if self.row_detail.visible {
+            render_row_detail(&self.row_detail, area, buf);
+        }
+
+        // Render fuzzy finder on top if visible
+        if self.finder.visible {
+            render_finder(&self.finder, area, buf);
         }
     }
 }
@@ -251,11 +293,37 @@ fn render_main_layout(app: &App, area: Rect, buf: &mut Buffer) {
 }
 
 fn render_global_status_bar(app: &App, area: Rect, buf: &mut Buffer) {
+    let pane = Span::styled(
+        format!("[{}]", app.focused_pane.label()),
+        Style::default().fg(BORDER_FOCUSED).bold(),
+    );
+
+    // A documented column under the cursor replaces the key hints
+    if let Some((label, column)) = app.selected_column()
+        && let Some(comment) = &column.comment
+    {
+        Paragraph::new(Line::from(vec![
+            pane,
+            Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+            Span::styled(label, Style::default().fg(TEXT_NORMAL)),
+            Span::styled(
+                format!(" {}", column.data_type),
+                Style::default().fg(TEXT_DIM),
+            ),
+            Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+            Span::styled(
+                comment.split_whitespace().collect::<Vec<_>>().join(" "),
+                Style::default().fg(TEXT_DIM).italic(),
+            ),
+        ]))
+        .style(Style::default().bg(BG))
+        .alignment(Alignment::Center)
+        .render(area, buf);
+        return;
+    }
+
     Paragraph::new(Line::from(vec![
-        Span::styled(
-            format!("[{}]", app.focused_pane.label()),
-            Style::default().fg(BORDER_FOCUSED).bold(),
-        ),
+        pane,
         Span::styled(" │ ", Style::default().fg(SEPARATOR)),
         Span::styled("Tab", Style::default().fg(TEXT_NORMAL)),
         Span::styled(" cycle  ", Style::default().fg(TEXT_DIM)),
@@ -277,7 +345,15 @@ fn render_sidebar(app: &App, area: Rect, buf: &mut Buffer) {
         _ => "database",
     };
 
-    let title = format!(" {} ", db_name);
+    let mut title = match app.table_sort {
+        TableSort::Name => format!(" {} ", db_name),
+        sort => format!(" {} · by {} ", db_name, sort.label()),
+    };
+    if app.schema_error.is_some() && app.db_structure.is_some() {
+        title.push_str("· load failed ⟳ ");
+    } else if app.schema_stale {
+        title.push_str("· stale ⟳ ");
+    }
     let block = pane_block(&title, app.focused_pane == FocusedPane::Sidebar);
     let inner = block.inner(area);
     block.render(area, buf);
@@ -285,7 +361,25 @@ fn render_sidebar(app: &App, area: Rect, buf: &mut Buffer) {
     let layout = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner);
     let (lines, selected_idx) = build_tree_lines(app);
 
-    if lines.is_empty() {
+    if lines.is_empty()
+        && let Some(error) = &app.schema_error
+    {
+        Paragraph::new(vec![
+            Line::from(Span::styled(
+                "Schema load failed",
+                Style::default().fg(TEXT_ERROR),
+            )),
+            Line::from(Span::styled(error.clone(), Style::default().fg(TEXT_DIM))),
+            Line::from(""),
+            Line::from(Span::styled(
+                "retrying…",
+                Style::default().fg(TEXT_DIM).italic(),
+            )),
+        ])
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .render(layout[0], buf);
+    } else if lines.is_empty() {
         let centered = Layout::vertical([
             Constraint::Fill(1),
             Constraint::Length(1),
@@ -322,21 +416,42 @@ fn render_sidebar(app: &App, area: Rect, buf: &mut Buffer) {
         .db_structure
         .as_ref()
         .map_or(0, |s| s.schemas.iter().map(|sc| sc.tables.len()).sum());
-    Paragraph::new(Span::styled(
-        format!("{} tables │ r refresh │ ←→ expand", table_count),
-        Style::default().fg(TEXT_DIM),
-    ))
-    .alignment(Alignment::Center)
-    .render(layout[1], buf);
+    let footer = match &app.tree_filter {
+        Some(filter) => Line::from(vec![
+            Span::styled("/", Style::default().fg(MATCH_COLOR)),
+            Span::styled(filter.query.clone(), Style::default().fg(MATCH_COLOR)),
+            Span::styled(
+                format!(" · {} matches │ Esc clear", filter.matched.len()),
+                Style::default().fg(TEXT_DIM),
+            ),
+        ]),
+        None => Line::from(Span::styled(
+            format!("{} tables │ / find │ d ddl │ e erd │ i import", table_count),
+            Style::default().fg(TEXT_DIM),
+        )),
+    };
+    Paragraph::new(footer)
+        .alignment(Alignment::Center)
+        .render(layout[1], buf);
 }
 
-fn build_tree_lines(app: &App) -> (Vec<Line<'static>>, Option<usize>) {
-    let Some(structure) = &app.db_structure else {
-        return (vec![], None);
-    };
+/// Dot after a tree node that changed on a recent schema refresh.
+fn changed_marker() -> Span<'static> {
+    Span::styled(" ●", Style::default().fg(BORDER_FOCUSED))
+}
 
+/// Dim trailing text for a table or column comment. Only the first line is
+/// shown; the row detail view and status bar have room for the rest.
+fn comment_span(comment: &str) -> Span<'static> {
+    let first = comment.lines().next().unwrap_or_default();
+    Span::styled(
+        format!("  -- {first}"),
+        Style::default().fg(TEXT_DIM).italic(),
+    )
+}
+
+fn build_tree_lines(app: &App) -> (Vec<Line<'static>>, Option<usize>) {
     let selected = app.tree_state.selected();
-    let opened = app.tree_state.opened();
     let selected_table = app.selected_table.as_ref();
 
     let db_name = match &app.connection {
@@ -347,115 +462,122 @@ fn build_tree_lines(app: &App) -> (Vec<Line<'static>>, Option<usize>) {
     let mut lines = Vec::new();
     let mut selected_idx = None;
 
-    let root_path = vec![TreeNodeId::Root];
-    let root_expanded = opened.iter().any(|p| p == &root_path);
-    if selected == root_path {
-        selected_idx = Some(lines.len());
-    }
-    lines.push(tree_line(
-        0,
-        root_expanded,
-        true,
-        selected == root_path,
-        vec![
-            Span::styled("󰆼 ", Style::default().fg(TEXT_SUCCESS)),
-            Span::styled(db_name, Style::default().fg(TEXT_NORMAL).bold()),
-        ],
-    ));
-
-    if !root_expanded {
-        return (lines, selected_idx);
-    }
-
-    for schema in &structure.schemas {
-        let schema_path = vec![TreeNodeId::Root, TreeNodeId::Schema(schema.name.clone())];
-        let schema_expanded = opened.iter().any(|p| p == &schema_path);
-        let is_selected = selected == schema_path;
+    for node in app.visible_tree_nodes() {
+        let is_selected = selected == node.path.as_slice();
         if is_selected {
             selected_idx = Some(lines.len());
         }
 
-        let icon = if schema.name == "public" {
-            "󰉖 "
-        } else {
-            "󰉋 "
-        };
-        lines.push(tree_line(
-            1,
-            schema_expanded,
-            !schema.tables.is_empty(),
-            is_selected,
-            vec![
-                Span::styled(icon, Style::default().fg(ICON_GRAY)),
-                Span::styled(schema.name.clone(), Style::default().fg(TEXT_NORMAL)),
+        let mut content = match node.item {
+            TreeItem::Root => vec![
+                Span::styled("󰆼 ", Style::default().fg(TEXT_SUCCESS)),
+                Span::styled(db_name.clone(), Style::default().fg(TEXT_NORMAL).bold()),
+            ],
+            TreeItem::Schema(schema) => {
+                let icon = if schema.name == "public" {
+                    "󰉖 "
+                } else {
+                    "󰉋 "
+                };
+                let mut spans = vec![
+                    Span::styled(icon, Style::default().fg(ICON_GRAY)),
+                    Span::styled(schema.name.clone(), Style::default().fg(TEXT_NORMAL)),
+                    Span::styled(
+                        format!(" ({})", schema.tables.len()),
+                        Style::default().fg(TEXT_DIM),
+                    ),
+                ];
+                if app.recently_changed(&schema.name, None) {
+                    spans.push(changed_marker());
+                }
+                spans
+            }
+            TreeItem::Table(table) | TreeItem::View(table) => {
+                let schema = match node.path.get(1) {
+                    Some(TreeNodeId::Schema(schema)) => schema.as_str(),
+                    _ => "",
+                };
+                let is_viewing =
+                    selected_table.is_some_and(|(s, t)| s == schema && t == &table.name);
+                let style = if is_viewing {
+                    Style::default().fg(TEXT_SUCCESS).bold()
+                } else {
+                    Style::default().fg(TEXT_NORMAL)
+                };
+                let icon = if matches!(node.item, TreeItem::View(_)) {
+                    "󰈈 "
+                } else {
+                    "󰓫 "
+                };
+                let mut spans = vec![
+                    Span::styled(icon, style),
+                    Span::styled(table.name.clone(), style),
+                ];
+                if app.recently_changed(schema, Some(&table.name)) {
+                    spans.push(changed_marker());
+                }
+                if let Some(stats) = table.stats {
+                    let rows = stats
+                        .row_estimate
+                        .map_or_else(|| "?".to_string(), compact_count);
+                    spans.push(Span::styled(
+                        format!(
+                            "  ~{rows} · {} · idx {}",
+                            compact_bytes(stats.total_bytes),
+                            compact_bytes(stats.index_bytes)
+                        ),
+                        Style::default().fg(TEXT_DIM),
+                    ));
+                }
+                if let Some(comment) = &table.comment {
+                    spans.push(comment_span(comment));
+                }
+                spans
+            }
+            TreeItem::Category(schema, category) => vec![
+                Span::styled("󰉋 ", Style::default().fg(ICON_GRAY)),
+                Span::styled(category.label(), Style::default().fg(ICON_GRAY)),
                 Span::styled(
-                    format!(" ({})", schema.tables.len()),
+                    format!(" ({})", category.count(schema)),
                     Style::default().fg(TEXT_DIM),
                 ),
             ],
-        ));
-
-        if !schema_expanded {
-            continue;
-        }
-
-        for table in &schema.tables {
-            let table_path = vec![
-                TreeNodeId::Root,
-                TreeNodeId::Schema(schema.name.clone()),
-                TreeNodeId::Table {
-                    schema: schema.name.clone(),
-                    table: table.name.clone(),
-                },
-            ];
-            let table_expanded = opened.iter().any(|p| p == &table_path);
-            let is_selected = selected == table_path;
-            if is_selected {
-                selected_idx = Some(lines.len());
-            }
-
-            let is_viewing =
-                selected_table.is_some_and(|(s, t)| s == &schema.name && t == &table.name);
-            let style = if is_viewing {
-                Style::default().fg(TEXT_SUCCESS).bold()
-            } else {
-                Style::default().fg(TEXT_NORMAL)
-            };
-
-            lines.push(tree_line(
-                2,
-                table_expanded,
-                !table.columns.is_empty(),
-                is_selected,
+            TreeItem::Routine(routine) => {
+                let signature = if routine.result.is_empty() {
+                    format!("({})", routine.arguments)
+                } else {
+                    format!("({}) → {}", routine.arguments, routine.result)
+                };
+                let icon = match routine.kind {
+                    RoutineKind::Function => "󰊕 ",
+                    RoutineKind::Procedure => "󰡱 ",
+                };
                 vec![
-                    Span::styled("󰓫 ", style),
-                    Span::styled(table.name.clone(), style),
-                ],
-            ));
-
-            if !table_expanded {
-                continue;
+                    Span::styled(icon, Style::default().fg(ICON_GRAY)),
+                    Span::styled(routine.name.clone(), Style::default().fg(TEXT_NORMAL)),
+                    Span::styled(signature, Style::default().fg(TEXT_DIM)),
+                ]
             }
-
-            for col in &table.columns {
-                let col_path = vec![
-                    TreeNodeId::Root,
-                    TreeNodeId::Schema(schema.name.clone()),
-                    TreeNodeId::Table {
-                        schema: schema.name.clone(),
-                        table: table.name.clone(),
-                    },
-                    TreeNodeId::Column {
-                        schema: schema.name.clone(),
-                        table: table.name.clone(),
-                        column: col.name.clone(),
-                    },
-                ];
-                let is_selected = selected == col_path;
-                if is_selected {
-                    selected_idx = Some(lines.len());
+            TreeItem::Sequence(sequence) => {
+                let mut detail = format!(" {}", sequence.data_type);
+                if let Some(last) = &sequence.last_value {
+                    detail.push_str(&format!(" @ {last}"));
                 }
-
+                vec![
+                    Span::styled("# ", Style::default().fg(ICON_GRAY)),
+                    Span::styled(sequence.name.clone(), Style::default().fg(TEXT_NORMAL)),
+                    Span::styled(detail, Style::default().fg(TEXT_DIM)),
+                ]
+            }
+            TreeItem::UserType(user_type) => vec![
+                Span::styled("◈ ", Style::default().fg(ICON_GRAY)),
+                Span::styled(user_type.name.clone(), Style::default().fg(TEXT_NORMAL)),
+                Span::styled(
+                    format!(" {} ({})", user_type.kind, user_type.detail),
+                    Style::default().fg(TEXT_DIM),
+                ),
+            ],
+            TreeItem::Column(col) => {
                 let icon = if col.is_primary_key {
                     Span::styled("󰌋 ", Style::default().fg(PK_COLOR))
                 } else if col.is_nullable {
@@ -463,26 +585,138 @@ fn build_tree_lines(app: &App) -> (Vec<Line<'static>>, Option<usize>) {
                 } else {
                     Span::styled("• ", Style::default().fg(TEXT_NORMAL))
                 };
-
-                lines.push(tree_line(
-                    3,
-                    false,
-                    false,
-                    is_selected,
-                    vec![
-                        icon,
-                        Span::styled(col.name.clone(), Style::default().fg(TEXT_NORMAL)),
-                        Span::raw(" "),
-                        Span::styled(col.data_type.clone(), Style::default().fg(TEXT_DIM)),
-                    ],
-                ));
+                let mut spans = vec![
+                    icon,
+                    Span::styled(col.name.clone(), Style::default().fg(TEXT_NORMAL)),
+                    Span::raw(" "),
+                    Span::styled(col.data_type.clone(), Style::default().fg(TEXT_DIM)),
+                ];
+                if let Some(comment) = &col.comment {
+                    spans.push(comment_span(comment));
+                }
+                spans
+            }
+            TreeItem::Group(table, group) => {
+                let count = match group {
+                    TreeGroup::Indexes => table.indexes.len(),
+                    TreeGroup::Keys => table.foreign_keys.len() + table.constraints.len(),
+                };
+                vec![
+                    Span::styled("󰉋 ", Style::default().fg(ICON_GRAY)),
+                    Span::styled(group.label(), Style::default().fg(ICON_GRAY)),
+                    Span::styled(format!(" ({count})"), Style::default().fg(TEXT_DIM)),
+                ]
+            }
+            TreeItem::Index(index) => {
+                let icon = if index.is_primary {
+                    Span::styled("󰌋 ", Style::default().fg(PK_COLOR))
+                } else if index.is_unique {
+                    Span::styled("◆ ", Style::default().fg(TEXT_NORMAL))
+                } else {
+                    Span::styled("◇ ", Style::default().fg(TEXT_DIM))
+                };
+                let mut detail = format!(" ({})", index.columns.join(", "));
+                if index.is_unique && !index.is_primary {
+                    detail.push_str(" unique");
+                }
+                if !matches!(index.method.as_str(), "btree" | "") {
+                    detail.push_str(&format!(" {}", index.method));
+                }
+                vec![
+                    icon,
+                    Span::styled(index.name.clone(), Style::default().fg(TEXT_NORMAL)),
+                    Span::styled(detail, Style::default().fg(TEXT_DIM)),
+                ]
+            }
+            TreeItem::ForeignKey(fk) => {
+                let schema = match node.path.get(1) {
+                    Some(TreeNodeId::Schema(schema)) => schema.as_str(),
+                    _ => "",
+                };
+                let target = if fk.ref_schema == schema {
+                    fk.ref_table.clone()
+                } else {
+                    format!("{}.{}", fk.ref_schema, fk.ref_table)
+                };
+                vec![
+                    Span::styled("→ ", Style::default().fg(PK_COLOR)),
+                    Span::styled(fk.name.clone(), Style::default().fg(TEXT_NORMAL)),
+                    Span::styled(
+                        format!(
+                            " ({}) → {}({})",
+                            fk.columns.join(", "),
+                            target,
+                            fk.ref_columns.join(", ")
+                        ),
+                        Style::default().fg(TEXT_DIM),
+                    ),
+                ]
+            }
+            TreeItem::Constraint(constraint) => {
+                let icon = match constraint.kind {
+                    ConstraintKind::Check => "✓ ",
+                    ConstraintKind::Unique => "◆ ",
+                };
+                vec![
+                    Span::styled(icon, Style::default().fg(ICON_GRAY)),
+                    Span::styled(constraint.name.clone(), Style::default().fg(TEXT_NORMAL)),
+                    Span::raw(" "),
+                    Span::styled(constraint.definition.clone(), Style::default().fg(TEXT_DIM)),
+                ]
             }
+        };
+
+        // Nodes matched by the finder filter get their name highlighted
+        if app
+            .tree_filter
+            .as_ref()
+            .is_some_and(|f| f.matched.contains(&node.path))
+            && let Some(name) = content.get_mut(1)
+        {
+            name.style = name.style.fg(MATCH_COLOR).underlined();
         }
+
+        lines.push(tree_line(
+            node.path.len() - 1,
+            node.expanded,
+            node.item.has_children(),
+            is_selected,
+            content,
+        ));
     }
 
     (lines, selected_idx)
 }
 
+/// Row count shortened to at most four characters, e.g. `12k`, `3.4M`.
+fn compact_count(n: i64) -> String {
+    let n = n as f64;
+    match n {
+        _ if n < 1e3 => format!("{n}"),
+        _ if n < 1e4 => format!("{:.1}k", n / 1e3),
+        _ if n < 1e6 => format!("{:.0}k", n / 1e3),
+        _ if n < 1e7 => format!("{:.1}M", n / 1e6),
+        _ if n < 1e9 => format!("{:.0}M", n / 1e6),
+        _ => format!("{:.1}G", n / 1e9),
+    }
+}
+
+/// Byte size with a binary unit suffix, e.g. `16K`, `1.2G`.
+fn compact_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 || value >= 10.0 {
+        format!("{value:.0}{}", UNITS[unit])
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
 fn tree_line(
     depth: usize,
     expanded: bool,
@@ -511,80 +745,415 @@ fn tree_line(
 }
 
 fn render_stats_panel(app: &App, area: Rect, buf: &mut Buffer) {
-    let block = pane_block(" ◉ Live Monitor ", app.focused_pane == FocusedPane::Stats);
+    let focused = app.focused_pane == FocusedPane::Stats;
+    let title = if app.stats.show_latency {
+        " ◉ Live Monitor · latency "
+    } else if app.stats.showing_server() {
+        " ◉ Live Monitor · server "
+    } else {
+        " ◉ Live Monitor · client "
+    };
+    let mut block = pane_block(title, focused);
+    if focused {
+        block = block
+            .title(
+                Line::from(format!(
+                    " v series  g {}  p {}  ←→ inspect ",
+                    app.stats.graph_mode.toggle().label(),
+                    if app.stats.show_latency {
+                        "graphs"
+                    } else {
+                        "latency"
+                    }
+                ))
+                .right_aligned(),
+            )
+            .title_bottom(
+                Line::from(" s sessions  l locks  t top queries  h health ").right_aligned(),
+            );
+    }
     let inner = block.inner(area);
     block.render(area, buf);
 
     let layout = Layout::vertical([Constraint::Min(4), Constraint::Length(3)]).split(inner);
-    render_ascii_graphs(app, layout[0], buf);
+    if app.stats.show_latency {
+        render_latency_breakdown(app, layout[0], buf);
+    } else {
+        render_ascii_graphs(app, layout[0], buf);
+    }
     render_stats_info(app, layout[1], buf);
 }
 
-fn render_ascii_graphs(app: &App, area: Rect, buf: &mut Buffer) {
-    let rows = Layout::vertical([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).split(area);
-    let top_cols =
-        Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).split(rows[0]);
-    let bottom_cols =
-        Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).split(rows[1]);
+/// Percentiles of this session's queries by statement kind, over the
+/// distribution of all of them.
+fn render_latency_breakdown(app: &App, area: Rect, buf: &mut Buffer) {
+    let latency = &app.stats.latency;
+    let layout = Layout::vertical([
+        Constraint::Length(StatementKind::ALL.len() as u16 + 2),
+        Constraint::Min(0),
+    ])
+    .split(area);
 
-    render_graph(
-        "qps",
-        &app.stats.queries_per_sec,
-        &make_color_fn(50, true),
-        top_cols[0],
-        buf,
+    let dim = Style::default().fg(TEXT_DIM);
+    let header = Row::new(
+        ["Kind", "n", "p50", "p95", "p99", "max"]
+            .map(|col| Cell::from(col).style(Style::default().fg(TEXT_NORMAL).bold())),
     );
+    let ms_cell = |ms: Option<u64>| match ms {
+        Some(ms) => Cell::from(format_duration(ms as f64 / 1000.0)).style(latency_style(ms.max(1))),
+        None => Cell::from("—").style(dim),
+    };
+    let rows: Vec<Row> = std::iter::once(("all", &latency.all))
+        .chain(
+            StatementKind::ALL
+                .iter()
+                .map(|kind| (kind.label(), latency.kind(*kind))),
+        )
+        .map(|(label, histogram)| {
+            let seen = histogram.count() > 0;
+            Row::new(vec![
+                Cell::from(label).style(Style::default().fg(TEXT_NORMAL)),
+                Cell::from(compact_count(histogram.count() as i64))
+                    .style(Style::default().fg(NUMBER_COLOR)),
+                ms_cell(histogram.percentile(0.50)),
+                ms_cell(histogram.percentile(0.95)),
+                ms_cell(histogram.percentile(0.99)),
+                ms_cell(seen.then(|| histogram.max())),
+            ])
+        })
+        .collect();
+    Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .render(layout[0], buf);
+
+    if layout[1].height < 3 {
+        return;
+    }
+    let distribution = latency.all.distribution();
+    let bar_width = (layout[1].width / distribution.len() as u16)
+        .saturating_sub(1)
+        .max(1);
+    let bars: Vec<Bar> = distribution
+        .iter()
+        .enumerate()
+        .map(|(bin, count)| {
+            let label = match DISTRIBUTION_BOUNDS.get(bin) {
+                Some(1000) => "<1s".to_string(),
+                Some(bound) if bin == 0 => format!("<{bound}ms"),
+                Some(bound) => format!("<{bound}"),
+                None => format!("{}s+", DISTRIBUTION_BOUNDS[bin - 1] / 1000),
+            };
+            // Colour each bin by the slowest query it can hold
+            let style = latency_style(DISTRIBUTION_BOUNDS.get(bin).copied().unwrap_or(u64::MAX));
+            Bar::default()
+                .value(*count)
+                .text_value(compact_count(*count as i64))
+                .label(Line::from(label))
+                .style(style)
+                .value_style(style.reversed())
+        })
+        .collect();
+    BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .label_style(dim)
+        .render(layout[1], buf);
+}
+
+fn render_ascii_graphs(app: &App, area: Rect, buf: &mut Buffer) {
+    if !app.stats.graphs.is_empty() {
+        render_configured_graphs(app, area, buf);
+        return;
+    }
+
+    let rows = Layout::vertical([
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+    ])
+    .split(area);
+    let cells: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).split(*row)
+        })
+        .collect();
+
+    let stats = &app.stats;
+    if stats.showing_server() {
+        render_graph(
+            app,
+            BuiltinMetric::ServerTps.label(app.db_type()),
+            &SeriesSource::Builtin(BuiltinMetric::ServerTps),
+            &make_color_fn(1000, true),
+            None,
+            cells[0][0],
+            buf,
+        );
+        render_graph(
+            app,
+            "read",
+            &SeriesSource::Builtin(BuiltinMetric::ServerReads),
+            &make_color_fn(100_000, true),
+            None,
+            cells[0][1],
+            buf,
+        );
+        render_graph(
+            app,
+            "write",
+            &SeriesSource::Builtin(BuiltinMetric::ServerWrites),
+            &make_color_fn(10_000, true),
+            None,
+            cells[1][0],
+            buf,
+        );
+    } else {
+        render_graph(
+            app,
+            "qps",
+            &SeriesSource::Builtin(BuiltinMetric::Qps),
+            &make_color_fn(50, true),
+            None,
+            cells[0][0],
+            buf,
+        );
+        render_graph(
+            app,
+            "rows",
+            &SeriesSource::Builtin(BuiltinMetric::Rows),
+            &make_color_fn(10000, true),
+            None,
+            cells[0][1],
+            buf,
+        );
+        render_graph(
+            app,
+            "ms",
+            &SeriesSource::Builtin(BuiltinMetric::LatencyMs),
+            &make_color_fn(300, false),
+            None,
+            cells[1][0],
+            buf,
+        );
+    }
     render_graph(
-        "rows",
-        &app.stats.rows_per_sec,
-        &make_color_fn(10000, true),
-        top_cols[1],
+        app,
+        if stats.pool.saturated {
+            "wait ms (pool full)"
+        } else {
+            "wait ms"
+        },
+        &SeriesSource::Builtin(BuiltinMetric::AcquireMs),
+        &make_color_fn(100, false),
+        None,
+        cells[1][1],
         buf,
     );
+    let pool_max = stats.pool.max as u64;
     render_graph(
-        "ms",
-        &app.stats.latency_ms,
-        &make_color_fn(300, false),
-        bottom_cols[0],
+        app,
+        &format!("pool ({} idle)", stats.pool.idle),
+        &SeriesSource::Builtin(BuiltinMetric::PoolInUse),
+        &make_color_fn(pool_max, false),
+        Some(pool_max),
+        cells[2][0],
         buf,
     );
     render_graph(
-        "conn",
-        &app.stats.connections,
-        &make_color_fn(20, true),
-        bottom_cols[1],
+        app,
+        "server conn",
+        &SeriesSource::Builtin(BuiltinMetric::ServerConnections),
+        &make_color_fn(stats.server_max_connections, false),
+        Some(stats.server_max_connections),
+        cells[2][1],
         buf,
     );
 }
 
-fn render_graph<F: Fn(u64, u64) -> Color>(
-    label: &str,
-    data: &std::collections::VecDeque<u64>,
-    color_fn: &F,
-    area: Rect,
-    buf: &mut Buffer,
-) {
+/// Lay the graphs from the config file out two to a row.
+fn render_configured_graphs(app: &App, area: Rect, buf: &mut Buffer) {
+    let graphs = &app.stats.graphs;
+    let row_count = graphs.len().div_ceil(2) as u32;
+    let rows =
+        Layout::vertical((0..row_count).map(|_| Constraint::Ratio(1, row_count))).split(area);
+
+    for (graph, cell) in graphs.iter().zip(rows.iter().flat_map(|row| {
+        Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .split(*row)
+            .to_vec()
+    })) {
+        if let [series] = graph.series.as_slice() {
+            let data = app.stats.series(&series.source);
+            let mut label = graph
+                .title
+                .clone()
+                .unwrap_or_else(|| series.label(app.db_type()));
+            if app.stats.is_stale(&series.source) {
+                label.push_str(" (stale)");
+            }
+            let cap = graph
+                .limit
+                .unwrap_or_else(|| data.iter().max().copied().unwrap_or(1));
+            render_graph(
+                app,
+                &label,
+                &series.source,
+                &make_color_fn(cap, graph.limit.is_none()),
+                graph.limit,
+                cell,
+                buf,
+            );
+        } else {
+            render_overlay_graph(app, graph, cell, buf);
+        }
+    }
+}
+
+/// Graph several series on one scale, each in its palette color, with a
+/// legend of their values in place of the single value.
+fn render_overlay_graph(app: &App, graph: &Graph, area: Rect, buf: &mut Buffer) {
     if area.height == 0 || area.width == 0 {
         return;
     }
 
+    let focused = app.focused_pane == FocusedPane::Stats;
+    let cursor = if focused { app.stats.graph_cursor } else { 0 };
     let layout = Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).split(area);
-    let current = data.back().copied().unwrap_or(0);
-    let max = data.iter().max().copied().unwrap_or(1).max(1);
-
-    Paragraph::new(Line::from(vec![
-        Span::styled(format!(" {} ", label), Style::default().fg(TEXT_DIM)),
-        Span::styled(
-            format!("{:>4}", current),
-            Style::default().fg(color_fn(current, max)).bold(),
-        ),
-    ]))
-    .render(layout[0], buf);
+    let series: Vec<_> = graph
+        .series
+        .iter()
+        .zip(PALETTE.iter().cycle())
+        .map(|(series, color)| (series, app.stats.series(&series.source), *color))
+        .collect();
+    let max = match graph.limit {
+        Some(limit) if limit > 0 => limit,
+        _ => series
+            .iter()
+            .filter_map(|(_, data, _)| data.iter().max().copied())
+            .max()
+            .unwrap_or(1)
+            .max(1),
+    };
 
-    AsciiDotGraph::new(data, max, color_fn)
-        .height(layout[1].height.max(1))
-        .render(layout[1], buf);
-}
+    let mut spans = Vec::new();
+    if let Some(title) = &graph.title {
+        spans.push(Span::styled(
+            format!(" {title}"),
+            Style::default().fg(TEXT_DIM),
+        ));
+    }
+    for (series, _, color) in &series {
+        spans.push(Span::styled(
+            format!(" {} ", series.label(app.db_type())),
+            Style::default().fg(*color),
+        ));
+        if cursor == 0 && app.stats.is_stale(&series.source) {
+            spans.push(Span::styled("stale", Style::default().fg(TEXT_DIM)));
+        } else if let Some(current) = app.stats.value_at(&series.source, cursor) {
+            spans.push(Span::styled(
+                current.to_string(),
+                Style::default().fg(*color).bold(),
+            ));
+        } else {
+            spans.push(Span::styled("—", Style::default().fg(TEXT_DIM)));
+        }
+    }
+    if focused && cursor > 0 {
+        spans.push(Span::styled(
+            format!(" @-{cursor}s"),
+            Style::default().fg(MATCH_COLOR),
+        ));
+    }
+    Paragraph::new(Line::from(spans)).render(layout[0], buf);
+
+    let (first_series, first, first_color) = series[0];
+    let mut widget = AsciiDotGraph::new(first, max, move |_, _| first_color)
+        .gaps(app.stats.gaps(&first_series.source));
+    for (series, data, color) in &series[1..] {
+        widget = widget.overlay(data, app.stats.gaps(&series.source), *color);
+    }
+    widget
+        .height(layout[1].height.max(1))
+        .mode(app.stats.graph_mode)
+        .y_labels(focused)
+        .time_marker(if focused { 10 } else { 0 })
+        .cursor(focused.then_some(cursor))
+        .render(layout[1], buf);
+}
+
+/// Graph a series with its latest value. With a `limit`, the value reads
+/// as `current/limit` and the graph is scaled to the limit, so a series
+/// close to exhaustion fills the graph. While the stats pane is focused the
+/// graph gains axis labels and ten-second markers, and the value follows
+/// the cursor; seconds the series has no value for read as `—`.
+fn render_graph<F: Fn(u64, u64) -> Color>(
+    app: &App,
+    label: &str,
+    source: &SeriesSource,
+    color_fn: &F,
+    limit: Option<u64>,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    if area.height == 0 || area.width == 0 {
+        return;
+    }
+
+    let focused = app.focused_pane == FocusedPane::Stats;
+    let cursor = app.stats.graph_cursor;
+    let layout = Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).split(area);
+    let data = app.stats.series(source);
+    let current = app.stats.value_at(source, if focused { cursor } else { 0 });
+    let max = match limit {
+        Some(limit) if limit > 0 => limit,
+        _ => data.iter().max().copied().unwrap_or(1).max(1),
+    };
+
+    let mut spans = vec![
+        Span::styled(format!(" {} ", label), Style::default().fg(TEXT_DIM)),
+        match current {
+            Some(current) => Span::styled(
+                format!("{:>4}", current),
+                Style::default().fg(color_fn(current, max)).bold(),
+            ),
+            None => Span::styled(format!("{:>4}", "—"), Style::default().fg(TEXT_DIM)),
+        },
+    ];
+    if let Some(limit) = limit.filter(|limit| *limit > 0) {
+        spans.push(Span::styled(
+            format!("/{limit}"),
+            Style::default().fg(TEXT_DIM),
+        ));
+    }
+    if focused && cursor > 0 {
+        spans.push(Span::styled(
+            format!(" @-{cursor}s"),
+            Style::default().fg(MATCH_COLOR),
+        ));
+    }
+    Paragraph::new(Line::from(spans)).render(layout[0], buf);
+
+    AsciiDotGraph::new(data, max, color_fn)
+        .gaps(app.stats.gaps(source))
+        .height(layout[1].height.max(1))
+        .mode(app.stats.graph_mode)
+        .y_labels(focused)
+        .time_marker(if focused { 10 } else { 0 })
+        .cursor(focused.then_some(cursor))
+        .render(layout[1], buf);
+}
 
 fn render_stats_info(app: &App, area: Rect, buf: &mut Buffer) {
     Paragraph::new(vec![
@@ -609,6 +1178,12 @@ fn render_stats_info(app: &App, area: Rect, buf: &mut Buffer) {
                 latency_style(app.stats.last_query_ms.unwrap_or(0) as u64),
             ),
             Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+            Span::styled("p95: ", Style::default().fg(TEXT_DIM)),
+            match app.stats.latency.all.percentile(0.95) {
+                Some(ms) => Span::styled(format!("{ms}ms"), latency_style(ms.max(1))),
+                None => Span::styled("—", Style::default().fg(TEXT_DIM)),
+            },
+            Span::styled(" │ ", Style::default().fg(SEPARATOR)),
             Span::styled("Total: ", Style::default().fg(TEXT_DIM)),
             Span::styled(
                 format!("{}", app.stats.queries_run),
@@ -660,6 +1235,13 @@ fn render_content_area(app: &App, area: Rect, buf: &mut Buffer) {
     } else {
         match &app.current_view {
             CurrentView::TableView(state) => render_table_view(state, app, area, buf),
+            CurrentView::Definition(state) => render_definition_view(state, app, area, buf),
+            CurrentView::Erd(state) => render_erd_view(state, app, area, buf),
+            CurrentView::SchemaDiff(state) => render_schema_diff(state, app, area, buf),
+            CurrentView::Sessions(state) => render_sessions(state, app, area, buf),
+            CurrentView::Locks(state) => render_locks(state, app, area, buf),
+            CurrentView::TopQueries(state) => render_top_queries(state, app, area, buf),
+            CurrentView::Health(state) => render_health(state, app, area, buf),
             _ => render_placeholder(app, area, buf),
         }
     }
@@ -700,7 +1282,10 @@ fn render_placeholder(app: &App, area: Rect, buf: &mut Buffer) {
 }
 
 fn render_table_view(state: &TableViewState, app: &App, area: Rect, buf: &mut Buffer) {
-    let title = format!(" {} ", state.table_name);
+    let title = match &state.filter {
+        Some(filter) => format!(" {} [{}] ", state.table_name, filter.describe()),
+        None => format!(" {} ", state.table_name),
+    };
     let block = pane_block(&title, app.focused_pane == FocusedPane::Results);
     let inner = block.inner(area);
     block.render(area, buf);
@@ -729,176 +1314,1150 @@ fn render_table_view(state: &TableViewState, app: &App, area: Rect, buf: &mut Bu
         );
     }
 
-    render_table_footer(state, app.export_status_text(), layout[1], buf);
+    render_table_footer(
+        state,
+        !app.table_history.is_empty(),
+        app.export_status_text().as_deref(),
+        layout[1],
+        buf,
+    );
 }
 
-fn render_query_results(qr: &QueryResultState, app: &App, area: Rect, buf: &mut Buffer) {
-    let title = if qr.error.is_some() {
-        " Query Error "
-    } else {
-        " Query Results "
-    };
-    let block = pane_block(title, app.focused_pane == FocusedPane::Results);
+fn render_definition_view(state: &DefinitionViewState, app: &App, area: Rect, buf: &mut Buffer) {
+    let title = format!(" {} ", state.title);
+    let block = pane_block(&title, app.focused_pane == FocusedPane::Results);
     let inner = block.inner(area);
     block.render(area, buf);
 
     let layout = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).split(inner);
 
     // Feed actual visible height back so key handlers use the real row count.
-    app.results_area_height
-        .set(layout[0].height.saturating_sub(1));
+    app.results_area_height.set(layout[0].height);
 
-    if let Some(error) = &qr.error {
-        Paragraph::new(error.clone())
-            .style(Style::default().fg(TEXT_ERROR))
-            .wrap(Wrap { trim: false })
-            .render(layout[0], buf);
-    } else if qr.rows.is_empty() {
-        if qr.columns.is_empty() {
-            render_centered_message(
-                layout[0],
-                buf,
-                "✓ ",
-                "Query executed successfully",
-                TEXT_SUCCESS,
-            );
-        } else {
-            render_centered_message(layout[0], buf, "", "<no rows returned>", TEXT_DIM);
-        }
-    } else if qr.is_explain {
-        let lines: Vec<Line> = qr
-            .rows
+    if state.loading {
+        render_centered_message(layout[0], buf, "⟳ ", "Loading...", TEXT_NORMAL);
+    } else if let Some(error) = &state.error {
+        render_centered_message(layout[0], buf, "✗ ", error, TEXT_ERROR);
+    } else {
+        let lines: Vec<Line> = state
+            .lines
             .iter()
-            .map(|row| {
-                Line::from(Span::styled(
-                    row.first().map(|s| s.as_str()).unwrap_or(""),
-                    Style::default().fg(TEXT_NORMAL),
-                ))
-            })
+            .enumerate()
+            .skip(state.scroll)
+            .take(layout[0].height as usize)
+            .map(|(idx, line)| highlight_sql_line(line, idx, (usize::MAX, 0), false))
             .collect();
-        Paragraph::new(lines)
-            .wrap(Wrap { trim: false })
-            .render(layout[0], buf);
+        Paragraph::new(lines).render(layout[0], buf);
+    }
+
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("{} lines", state.lines.len()),
+            Style::default().fg(TEXT_NORMAL),
+        ),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled("↑↓", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" scroll  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("e", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" edit in SQL  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("b", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" back", Style::default().fg(TEXT_DIM)),
+    ]))
+    .alignment(Alignment::Center)
+    .render(layout[1], buf);
+}
+
+fn render_erd_view(state: &ErdViewState, app: &App, area: Rect, buf: &mut Buffer) {
+    let title = format!(" {} · ERD ", state.erd.schema);
+    let block = pane_block(&title, app.focused_pane == FocusedPane::Results);
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let layout = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).split(inner);
+    app.results_area_height.set(layout[0].height);
+    app.results_area_width.set(layout[0].width);
+
+    if state.erd.table_count() == 0 {
+        render_centered_message(layout[0], buf, "", "<no tables>", TEXT_DIM);
     } else {
-        render_data_table(
-            &qr.columns,
-            &qr.rows,
-            qr.selected_row,
-            qr.scroll_offset,
-            qr.col_offset,
-            layout[0],
-            buf,
-        );
+        let canvas = &state.canvas;
+        let width = layout[0].width as usize;
+        let lines: Vec<Line> = (state.pan_y..canvas.height)
+            .take(layout[0].height as usize)
+            .map(|y| {
+                let row = canvas.row(y);
+                let visible =
+                    &row[state.pan_x.min(row.len())..(state.pan_x + width).min(row.len())];
+                let mut spans = Vec::new();
+                let mut run = String::new();
+                let mut run_kind = None;
+                for &(ch, kind) in visible {
+                    if run_kind != Some(kind) && !run.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut run), erd_style(run_kind)));
+                    }
+                    run_kind = Some(kind);
+                    run.push(ch);
+                }
+                if !run.is_empty() {
+                    spans.push(Span::styled(run, erd_style(run_kind)));
+                }
+                Line::from(spans)
+            })
+            .collect();
+        Paragraph::new(lines).render(layout[0], buf);
     }
 
     if let Some(msg) = app.export_status_text() {
-        let color = if msg.starts_with('✓') {
-            TEXT_SUCCESS
-        } else {
-            TEXT_ERROR
-        };
-        Paragraph::new(Span::styled(msg, Style::default().fg(color)))
-            .alignment(Alignment::Center)
-            .render(layout[1], buf);
-    } else {
-        Paragraph::new(Line::from(vec![
-            Span::styled(
-                format!("{} rows", qr.row_count),
-                Style::default().fg(TEXT_NORMAL),
-            ),
-            Span::styled(" │ ", Style::default().fg(SEPARATOR)),
-            Span::styled(
-                format!("{}ms", qr.duration_ms),
-                Style::default().fg(TEXT_SUCCESS),
-            ),
-            Span::styled(" │ ", Style::default().fg(SEPARATOR)),
-            Span::styled("c", Style::default().fg(TEXT_NORMAL)),
-            Span::styled(" clear  ", Style::default().fg(TEXT_DIM)),
-            Span::styled("x", Style::default().fg(TEXT_NORMAL)),
-            Span::styled(" export", Style::default().fg(TEXT_DIM)),
-        ]))
+        Paragraph::new(Span::styled(
+            msg.as_str(),
+            Style::default().fg(status_color(&msg)),
+        ))
         .alignment(Alignment::Center)
         .render(layout[1], buf);
+        return;
     }
-}
 
-fn render_centered_message(area: Rect, buf: &mut Buffer, prefix: &str, msg: &str, color: Color) {
-    let centered = Layout::vertical([
-        Constraint::Fill(1),
-        Constraint::Length(1),
-        Constraint::Fill(1),
-    ])
-    .split(area);
     Paragraph::new(Line::from(vec![
-        Span::styled(prefix, Style::default().fg(color)),
-        Span::styled(msg, Style::default().fg(color)),
+        Span::styled(
+            format!(
+                "{} tables · {} relationships",
+                state.erd.table_count(),
+                state.erd.relationship_count()
+            ),
+            Style::default().fg(TEXT_NORMAL),
+        ),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled("hjkl", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" pan  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("+-", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(
+            format!(" zoom ({})  ", state.detail.label()),
+            Style::default().fg(TEXT_DIM),
+        ),
+        Span::styled("m", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" Mermaid  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("g", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" DOT  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("b", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" back", Style::default().fg(TEXT_DIM)),
     ]))
     .alignment(Alignment::Center)
-    .render(centered[1], buf);
+    .render(layout[1], buf);
 }
 
-fn render_data_table(
-    columns: &[String],
-    rows: &[Vec<String>],
-    selected_row: usize,
-    scroll_offset: usize,
-    col_offset: usize,
-    area: Rect,
-    buf: &mut Buffer,
-) {
-    if columns.is_empty() {
-        return;
-    }
+fn render_schema_diff(state: &SchemaDiffViewState, app: &App, area: Rect, buf: &mut Buffer) {
+    let title = format!(" Schema diff · {} → {} ", state.source, state.target);
+    let block = pane_block(&title, app.focused_pane == FocusedPane::Results);
+    let inner = block.inner(area);
+    block.render(area, buf);
 
-    let mut col_widths: Vec<usize> = columns.iter().map(|c| c.len()).collect();
-    for row in rows {
-        for (i, cell) in row.iter().enumerate() {
-            if i < col_widths.len() {
-                col_widths[i] = col_widths[i].max(cell.len().min(30));
+    let layout = Layout::vertical([
+        Constraint::Min(3),
+        Constraint::Length(4),
+        Constraint::Length(1),
+    ])
+    .split(inner);
+    app.results_area_height.set(layout[0].height);
+
+    if state.loading {
+        render_centered_message(layout[0], buf, "⟳ ", "Loading both schemas...", TEXT_NORMAL);
+    } else if let Some(error) = &state.error {
+        render_centered_message(layout[0], buf, "✗ ", error, TEXT_ERROR);
+    } else if state.entries.is_empty() {
+        render_centered_message(layout[0], buf, "✓ ", "Schemas match", TEXT_SUCCESS);
+    } else {
+        // One header line per table, then its differences
+        let mut lines = Vec::new();
+        let mut selected_line = 0;
+        let mut group: Option<(&str, Option<&str>)> = None;
+        for (idx, entry) in state.entries.iter().enumerate() {
+            let key = (entry.schema.as_str(), entry.table.as_deref());
+            if group != Some(key) {
+                group = Some(key);
+                let label = match key {
+                    (schema, Some(table)) => format!("{schema}.{table}"),
+                    (schema, None) => schema.to_string(),
+                };
+                lines.push(Line::from(Span::styled(
+                    label,
+                    Style::default().fg(TEXT_NORMAL).bold(),
+                )));
+            }
+            let color = match entry.change {
+                Change::Missing => TEXT_SUCCESS,
+                Change::Extra => TEXT_ERROR,
+                Change::Changed => PK_COLOR,
+            };
+            let line = Line::from(vec![
+                Span::styled(
+                    format!("  {} ", entry.change.sign()),
+                    Style::default().fg(color),
+                ),
+                Span::styled(entry.describe(), Style::default().fg(TEXT_NORMAL)),
+            ]);
+            if idx == state.selected {
+                selected_line = lines.len();
+                lines.push(line.style(Style::default().bg(SELECTED_BG).fg(SELECTED_FG)));
+            } else {
+                lines.push(line);
             }
         }
-    }
-
-    // Apply column offset — skip the first col_offset columns
-    let col_offset = col_offset.min(columns.len().saturating_sub(1));
-    let vis_columns = &columns[col_offset..];
-    let vis_widths = &col_widths[col_offset..];
 
-    let constraints: Vec<Constraint> = vis_widths
-        .iter()
-        .map(|&w| Constraint::Length((w + 2) as u16))
-        .collect();
-    let header = Row::new(
-        vis_columns
-            .iter()
-            .map(|col| Cell::from(col.clone()).style(Style::default().fg(TEXT_NORMAL).bold())),
-    )
-    .height(1);
+        let height = layout[0].height as usize;
+        let scroll = (selected_line + 1).saturating_sub(height);
+        Paragraph::new(
+            lines
+                .into_iter()
+                .skip(scroll)
+                .take(height)
+                .collect::<Vec<_>>(),
+        )
+        .render(layout[0], buf);
 
-    let visible_rows = area.height.saturating_sub(1) as usize;
-    let end_idx = (scroll_offset + visible_rows).min(rows.len());
+        // Statements for the selected difference
+        if let (Some(entry), Some(db_type)) = (state.entries.get(state.selected), state.db_type) {
+            let sql: Vec<Line> = schema_diff::entry_sql(entry, db_type)
+                .iter()
+                .flat_map(|stmt| stmt.lines().map(String::from).collect::<Vec<_>>())
+                .enumerate()
+                .map(|(idx, line)| highlight_sql_line(&line, idx, (usize::MAX, 0), false))
+                .collect();
+            Paragraph::new(sql)
+                .block(
+                    Block::default()
+                        .borders(ratatui::widgets::Borders::TOP)
+                        .border_style(Style::default().fg(SEPARATOR)),
+                )
+                .render(layout[1], buf);
+        }
+    }
 
-    let data_rows: Vec<Row> = rows[scroll_offset..end_idx]
-        .iter()
-        .enumerate()
-        .map(|(visible_idx, row)| {
-            let is_selected = scroll_offset + visible_idx == selected_row;
-            let row_style = if is_selected {
-                Style::default().bg(SELECTED_BG).fg(SELECTED_FG)
-            } else {
-                Style::default()
-            };
+    if let Some(msg) = app.export_status_text() {
+        Paragraph::new(Span::styled(
+            msg.as_str(),
+            Style::default().fg(status_color(&msg)),
+        ))
+        .alignment(Alignment::Center)
+        .render(layout[2], buf);
+        return;
+    }
 
-            let cells: Vec<Cell> = row
-                .iter()
-                .skip(col_offset)
-                .map(|cell| {
-                    let display = if cell.len() > 30 {
+    let count = |change| state.entries.iter().filter(|e| e.change == change).count();
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("+{}", count(Change::Missing)),
+            Style::default().fg(TEXT_SUCCESS),
+        ),
+        Span::styled(
+            format!(" -{}", count(Change::Extra)),
+            Style::default().fg(TEXT_ERROR),
+        ),
+        Span::styled(
+            format!(" ~{}", count(Change::Changed)),
+            Style::default().fg(PK_COLOR),
+        ),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled("↑↓", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" select  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("e", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" migration in SQL  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("y", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" copy migration  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("b", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" back", Style::default().fg(TEXT_DIM)),
+    ]))
+    .alignment(Alignment::Center)
+    .render(layout[2], buf);
+}
+
+fn render_sessions(state: &SessionsViewState, app: &App, area: Rect, buf: &mut Buffer) {
+    let visible = state.visible();
+    let title = format!(" Sessions · by {} ", state.sort.label());
+    let block = pane_block(&title, app.focused_pane == FocusedPane::Results);
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let layout = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).split(inner);
+    app.results_area_height
+        .set(layout[0].height.saturating_sub(1));
+
+    if state.loading && state.sessions.is_empty() {
+        render_centered_message(layout[0], buf, "⟳ ", "Loading sessions...", TEXT_NORMAL);
+    } else if let Some(error) = &state.error {
+        render_centered_message(layout[0], buf, "✗ ", error, TEXT_ERROR);
+    } else if visible.is_empty() {
+        render_centered_message(layout[0], buf, "", "<no matching sessions>", TEXT_DIM);
+    } else {
+        let header = Row::new(
+            [
+                "PID", "User", "Client", "DB", "State", "Wait", "Time", "Query",
+            ]
+            .map(|col| Cell::from(col).style(Style::default().fg(TEXT_NORMAL).bold())),
+        );
+        let height = layout[0].height.saturating_sub(1) as usize;
+        let scroll = (state.selected + 1).saturating_sub(height);
+        let rows: Vec<Row> = visible
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(height)
+            .map(|(idx, session)| {
+                let state_color = match session.state.as_str() {
+                    "active" | "Query" => TEXT_SUCCESS,
+                    s if s.starts_with("idle in transaction") => PK_COLOR,
+                    _ => TEXT_DIM,
+                };
+                let query = session
+                    .query
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let row = Row::new(vec![
+                    Cell::from(session.pid.to_string()).style(Style::default().fg(NUMBER_COLOR)),
+                    Cell::from(session.user.clone()).style(Style::default().fg(TEXT_NORMAL)),
+                    Cell::from(session.client.clone()).style(Style::default().fg(TEXT_DIM)),
+                    Cell::from(session.database.clone()).style(Style::default().fg(TEXT_NORMAL)),
+                    Cell::from(session.state.clone()).style(Style::default().fg(state_color)),
+                    Cell::from(session.wait_event.clone()).style(Style::default().fg(PK_COLOR)),
+                    Cell::from(
+                        session
+                            .duration_secs
+                            .map(format_duration)
+                            .unwrap_or_default(),
+                    )
+                    .style(Style::default().fg(TEXT_NORMAL)),
+                    Cell::from(query).style(Style::default().fg(TEXT_DIM)),
+                ]);
+                if idx == state.selected {
+                    row.style(Style::default().bg(SELECTED_BG).fg(SELECTED_FG))
+                } else {
+                    row
+                }
+            })
+            .collect();
+        Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(12),
+                Constraint::Length(15),
+                Constraint::Length(12),
+                Constraint::Length(20),
+                Constraint::Length(22),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .render(layout[0], buf);
+    }
+
+    if let Some((action, pid)) = state.confirm {
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} {pid}? ", action.label()),
+                Style::default().fg(TEXT_ERROR).bold(),
+            ),
+            Span::styled("y", Style::default().fg(TEXT_NORMAL)),
+            Span::styled(" confirm  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("any key", Style::default().fg(TEXT_NORMAL)),
+            Span::styled(" abort", Style::default().fg(TEXT_DIM)),
+        ]))
+        .alignment(Alignment::Center)
+        .render(layout[1], buf);
+        return;
+    }
+
+    if state.editing_filter {
+        Paragraph::new(Line::from(vec![
+            Span::styled("/", Style::default().fg(BORDER_FOCUSED)),
+            Span::styled(state.filter.as_str(), Style::default().fg(TEXT_NORMAL)),
+            Span::styled("█", Style::default().fg(BORDER_FOCUSED)),
+        ]))
+        .render(layout[1], buf);
+        return;
+    }
+
+    if let Some(msg) = app.export_status_text() {
+        Paragraph::new(Span::styled(
+            msg.as_str(),
+            Style::default().fg(status_color(&msg)),
+        ))
+        .alignment(Alignment::Center)
+        .render(layout[1], buf);
+        return;
+    }
+
+    let active = visible.iter().filter(|s| !s.is_idle()).count();
+    let mut spans = vec![
+        Span::styled(
+            format!("{}", visible.len()),
+            Style::default().fg(TEXT_NORMAL),
+        ),
+        Span::styled(
+            format!(" sessions, {active} busy"),
+            Style::default().fg(TEXT_DIM),
+        ),
+    ];
+    if !state.filter.is_empty() {
+        spans.push(Span::styled(
+            format!(" matching /{}", state.filter),
+            Style::default().fg(MATCH_COLOR),
+        ));
+    }
+    spans.extend([
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled("s", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" sort  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("/", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" filter  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("i", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(
+            if state.hide_idle {
+                " show idle  "
+            } else {
+                " hide idle  "
+            },
+            Style::default().fg(TEXT_DIM),
+        ),
+        Span::styled("C", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" cancel  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("K", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" terminate  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("L", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" locks  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("b", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" back", Style::default().fg(TEXT_DIM)),
+    ]);
+    Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Center)
+        .render(layout[1], buf);
+}
+
+fn render_locks(state: &LocksViewState, app: &App, area: Rect, buf: &mut Buffer) {
+    let block = pane_block(
+        " Locks · blocking chains ",
+        app.focused_pane == FocusedPane::Results,
+    );
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let layout = Layout::vertical([
+        Constraint::Min(3),
+        Constraint::Length(4),
+        Constraint::Length(1),
+    ])
+    .split(inner);
+    app.results_area_height.set(layout[0].height);
+
+    if state.loading && state.nodes.is_empty() {
+        render_centered_message(layout[0], buf, "⟳ ", "Loading locks...", TEXT_NORMAL);
+    } else if let Some(error) = &state.error {
+        render_centered_message(layout[0], buf, "✗ ", error, TEXT_ERROR);
+    } else if state.nodes.is_empty() {
+        render_centered_message(
+            layout[0],
+            buf,
+            "✓ ",
+            "No sessions are waiting on locks",
+            TEXT_SUCCESS,
+        );
+    } else {
+        let height = layout[0].height as usize;
+        let scroll = (state.selected + 1).saturating_sub(height);
+        let lines: Vec<Line> = state
+            .nodes
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(height)
+            .map(|(idx, node)| {
+                let session = state.session(node.pid);
+                let mut spans = vec![
+                    Span::styled(
+                        match node.depth {
+                            0 => "● ".to_string(),
+                            depth => format!("{}└ ", "  ".repeat(depth)),
+                        },
+                        Style::default().fg(if node.depth == 0 {
+                            TEXT_ERROR
+                        } else {
+                            SEPARATOR
+                        }),
+                    ),
+                    Span::styled(node.pid.to_string(), Style::default().fg(NUMBER_COLOR)),
+                    Span::styled(
+                        format!(
+                            " {} {}",
+                            session.map(|s| s.user.as_str()).unwrap_or("?"),
+                            session
+                                .and_then(|s| s.duration_secs)
+                                .map(format_duration)
+                                .unwrap_or_default()
+                        ),
+                        Style::default().fg(TEXT_DIM),
+                    ),
+                ];
+                match &node.wait {
+                    Some(wait) => {
+                        spans.push(Span::styled(
+                            format!("  waits {} on ", wait.mode),
+                            Style::default().fg(TEXT_NORMAL),
+                        ));
+                        spans.push(Span::styled(
+                            wait.target.clone(),
+                            Style::default().fg(PK_COLOR),
+                        ));
+                        spans.push(Span::styled(
+                            if wait.held.is_empty() {
+                                format!(" (queued behind {})", wait.blocker)
+                            } else {
+                                format!(" ({} holds {})", wait.blocker, wait.held)
+                            },
+                            Style::default().fg(TEXT_DIM),
+                        ));
+                    }
+                    None => spans.push(Span::styled(
+                        format!(
+                            "  {}",
+                            session.map(|s| s.state.as_str()).unwrap_or_default()
+                        ),
+                        Style::default().fg(TEXT_ERROR),
+                    )),
+                }
+                let line = Line::from(spans);
+                if idx == state.selected {
+                    line.style(Style::default().bg(SELECTED_BG).fg(SELECTED_FG))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(layout[0], buf);
+
+        // Query of the selected session
+        if let Some(session) = state
+            .nodes
+            .get(state.selected)
+            .and_then(|node| state.session(node.pid))
+        {
+            let query = session
+                .query
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            Paragraph::new(Span::styled(query, Style::default().fg(TEXT_NORMAL)))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(ratatui::widgets::Borders::TOP)
+                        .border_style(Style::default().fg(SEPARATOR)),
+                )
+                .render(layout[1], buf);
+        }
+    }
+
+    if let Some(msg) = app.export_status_text() {
+        Paragraph::new(Span::styled(
+            msg.as_str(),
+            Style::default().fg(status_color(&msg)),
+        ))
+        .alignment(Alignment::Center)
+        .render(layout[2], buf);
+        return;
+    }
+
+    let heads = state.nodes.iter().filter(|n| n.depth == 0).count();
+    let waiting = state.nodes.len() - heads;
+    Paragraph::new(Line::from(vec![
+        Span::styled(format!("{waiting}"), Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" waiting in ", Style::default().fg(TEXT_DIM)),
+        Span::styled(format!("{heads}"), Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" chains", Style::default().fg(TEXT_DIM)),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled("↑↓", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" select  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("↵", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" go to session  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("r", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" refresh  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("b", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" back", Style::default().fg(TEXT_DIM)),
+    ]))
+    .alignment(Alignment::Center)
+    .render(layout[2], buf);
+}
+
+fn render_top_queries(state: &TopQueriesViewState, app: &App, area: Rect, buf: &mut Buffer) {
+    let title = format!(" Slow queries · by {} ", state.sort.label());
+    let block = pane_block(&title, app.focused_pane == FocusedPane::Results);
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let layout = Layout::vertical([
+        Constraint::Min(3),
+        Constraint::Length(5),
+        Constraint::Length(1),
+    ])
+    .split(inner);
+    app.results_area_height
+        .set(layout[0].height.saturating_sub(1));
+
+    if state.loading && state.stats.is_empty() {
+        render_centered_message(
+            layout[0],
+            buf,
+            "⟳ ",
+            "Loading statement statistics...",
+            TEXT_NORMAL,
+        );
+    } else if let Some(error) = &state.error {
+        render_centered_message(layout[0], buf, "✗ ", error, TEXT_ERROR);
+    } else if state.stats.is_empty() {
+        render_centered_message(layout[0], buf, "", "<no statements recorded>", TEXT_DIM);
+    } else {
+        let columns = [
+            ("Calls", Some(QuerySort::Calls)),
+            ("Total", Some(QuerySort::Total)),
+            ("Mean", Some(QuerySort::Mean)),
+            ("Rows", Some(QuerySort::Rows)),
+            ("Hit %", Some(QuerySort::HitRatio)),
+            ("Query", None),
+        ];
+        let header = Row::new(columns.map(|(name, sort)| {
+            let sorted = sort == Some(state.sort);
+            Cell::from(if sorted {
+                format!("{name} ▾")
+            } else {
+                name.to_string()
+            })
+            .style(
+                Style::default()
+                    .fg(if sorted { BORDER_FOCUSED } else { TEXT_NORMAL })
+                    .bold(),
+            )
+        }));
+        let height = layout[0].height.saturating_sub(1) as usize;
+        let scroll = (state.selected + 1).saturating_sub(height);
+        let rows: Vec<Row> = state
+            .stats
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(height)
+            .map(|(idx, stat)| {
+                let hit_color = match stat.hit_ratio {
+                    Some(ratio) if ratio < 0.9 => TEXT_ERROR,
+                    Some(ratio) if ratio < 0.99 => PK_COLOR,
+                    Some(_) => TEXT_SUCCESS,
+                    None => TEXT_DIM,
+                };
+                let query = stat.query.split_whitespace().collect::<Vec<_>>().join(" ");
+                let row = Row::new(vec![
+                    Cell::from(compact_count(stat.calls)).style(Style::default().fg(NUMBER_COLOR)),
+                    Cell::from(format_ms(stat.total_ms)).style(Style::default().fg(TEXT_NORMAL)),
+                    Cell::from(format_ms(stat.mean_ms)).style(Style::default().fg(TEXT_NORMAL)),
+                    Cell::from(compact_count(stat.rows)).style(Style::default().fg(NUMBER_COLOR)),
+                    Cell::from(
+                        stat.hit_ratio
+                            .map(|ratio| format!("{:.1}", ratio * 100.0))
+                            .unwrap_or_else(|| "—".to_string()),
+                    )
+                    .style(Style::default().fg(hit_color)),
+                    Cell::from(query).style(Style::default().fg(TEXT_DIM)),
+                ]);
+                if idx == state.selected {
+                    row.style(Style::default().bg(SELECTED_BG).fg(SELECTED_FG))
+                } else {
+                    row
+                }
+            })
+            .collect();
+        Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .render(layout[0], buf);
+
+        // Full text of the selected statement
+        if let Some(stat) = state.stats.get(state.selected) {
+            let sql: Vec<Line> = stat
+                .query
+                .lines()
+                .enumerate()
+                .map(|(idx, line)| highlight_sql_line(line, idx, (usize::MAX, 0), false))
+                .collect();
+            Paragraph::new(sql)
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .borders(ratatui::widgets::Borders::TOP)
+                        .border_style(Style::default().fg(SEPARATOR)),
+                )
+                .render(layout[1], buf);
+        }
+    }
+
+    if let Some(msg) = app.export_status_text() {
+        Paragraph::new(Span::styled(
+            msg.as_str(),
+            Style::default().fg(status_color(&msg)),
+        ))
+        .alignment(Alignment::Center)
+        .render(layout[2], buf);
+        return;
+    }
+
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("{}", state.stats.len()),
+            Style::default().fg(TEXT_NORMAL),
+        ),
+        Span::styled(" statements", Style::default().fg(TEXT_DIM)),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled("↑↓", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" select  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("s", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" sort  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("e", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" explain in SQL  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("r", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" refresh  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("b", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" back", Style::default().fg(TEXT_DIM)),
+    ]))
+    .alignment(Alignment::Center)
+    .render(layout[2], buf);
+}
+
+fn render_health(state: &HealthViewState, app: &App, area: Rect, buf: &mut Buffer) {
+    let block = pane_block(" Health ", app.focused_pane == FocusedPane::Results);
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let report = &state.report;
+    let dim = Style::default().fg(TEXT_DIM);
+
+    // Server-wide figures first, one line per replication link
+    let mut summary = vec![Line::from(vec![
+        Span::styled("Cache hit ratio  ", dim),
+        match report.cache_hit_ratio {
+            Some(ratio) => Span::styled(
+                format!("{:.2}%", ratio * 100.0),
+                Style::default()
+                    .fg(health_color((1.0 - ratio) * 100.0, 10))
+                    .bold(),
+            ),
+            None => Span::styled("—", dim),
+        },
+    ])];
+    if let Some(error) = &report.replication_error {
+        summary.push(Line::from(vec![
+            Span::styled("Replication  ", dim),
+            Span::styled(error.clone(), Style::default().fg(TEXT_ERROR)),
+        ]));
+    } else if report.replicas.is_empty() {
+        // A MySQL source can't list its replicas from the status read here
+        let status = match (report.is_replica, app.db_type()) {
+            (true, _) => "standby, but no WAL receiver is running",
+            (false, DbType::Postgres) => "no standbys attached",
+            (false, DbType::MySQL) => "not replicating",
+        };
+        summary.push(Line::from(vec![
+            Span::styled("Replication  ", dim),
+            Span::styled(status, dim),
+        ]));
+    }
+    for replica in &report.replicas {
+        let mut spans = vec![
+            Span::styled(
+                if report.is_replica {
+                    "Replica of  "
+                } else {
+                    "Standby  "
+                },
+                dim,
+            ),
+            Span::styled(replica.name.clone(), Style::default().fg(TEXT_NORMAL)),
+            Span::styled(format!("  {}  ", replica.state), dim),
+        ];
+        match replica.lag_secs {
+            Some(secs) => spans.push(Span::styled(
+                format!("lag {}", format_duration(secs)),
+                Style::default().fg(health_color(secs, 60)).bold(),
+            )),
+            None => spans.push(Span::styled("lag —", dim)),
+        }
+        if let Some(bytes) = replica.lag_bytes {
+            spans.push(Span::styled(
+                format!(" · {} behind", compact_bytes(bytes)),
+                dim,
+            ));
+        }
+        summary.push(Line::from(spans));
+    }
+
+    let layout = Layout::vertical([
+        Constraint::Length(summary.len() as u16 + 1),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .split(inner);
+    app.results_area_height
+        .set(layout[1].height.saturating_sub(1));
+
+    if state.loading && report.tables.is_empty() {
+        render_centered_message(inner, buf, "⟳ ", "Loading health...", TEXT_NORMAL);
+        return;
+    }
+    if let Some(error) = &state.error {
+        render_centered_message(inner, buf, "✗ ", error, TEXT_ERROR);
+        return;
+    }
+
+    Paragraph::new(summary)
+        .block(
+            Block::default()
+                .borders(ratatui::widgets::Borders::BOTTOM)
+                .border_style(Style::default().fg(SEPARATOR)),
+        )
+        .render(layout[0], buf);
+
+    let header = Row::new(
+        [
+            "Table",
+            "Live",
+            "Dead",
+            "Dead %",
+            "Idx use %",
+            "Vacuumed",
+            "Analyzed",
+        ]
+        .map(|col| Cell::from(col).style(Style::default().fg(TEXT_NORMAL).bold())),
+    );
+    let height = layout[1].height.saturating_sub(1) as usize;
+    let scroll = (state.selected + 1).saturating_sub(height);
+    let age = |secs: Option<f64>| match secs {
+        Some(secs) => Cell::from(format!("{} ago", format_duration(secs)))
+            .style(Style::default().fg(health_color(secs / 3600.0, 24 * 7))),
+        None if report.tracks_maintenance => Cell::from("never").style(dim),
+        None => Cell::from("—").style(dim),
+    };
+    let rows: Vec<Row> = report
+        .tables
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(height)
+        .map(|(idx, table)| {
+            let dead_ratio = table.dead_ratio();
+            let row = Row::new(vec![
+                Cell::from(format!("{}.{}", table.schema, table.table))
+                    .style(Style::default().fg(TEXT_NORMAL)),
+                Cell::from(compact_count(table.live_tuples))
+                    .style(Style::default().fg(NUMBER_COLOR)),
+                Cell::from(
+                    table
+                        .dead_tuples
+                        .map(compact_count)
+                        .unwrap_or_else(|| "—".into()),
+                )
+                .style(Style::default().fg(NUMBER_COLOR)),
+                match dead_ratio {
+                    Some(ratio) => Cell::from(format!("{:.1}", ratio * 100.0))
+                        .style(Style::default().fg(health_color(ratio * 100.0, 20))),
+                    None => Cell::from("—").style(dim),
+                },
+                match table.index_usage {
+                    Some(usage) => Cell::from(format!("{:.1}", usage * 100.0))
+                        .style(Style::default().fg(health_color((1.0 - usage) * 100.0, 50))),
+                    None => Cell::from("—").style(dim),
+                },
+                age(table.vacuum_age_secs),
+                age(table.analyze_age_secs),
+            ]);
+            if idx == state.selected {
+                row.style(Style::default().bg(SELECTED_BG).fg(SELECTED_FG))
+            } else {
+                row
+            }
+        })
+        .collect();
+    Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(14),
+            Constraint::Length(14),
+        ],
+    )
+    .header(header)
+    .render(layout[1], buf);
+
+    if let Some(msg) = app.export_status_text() {
+        Paragraph::new(Span::styled(
+            msg.as_str(),
+            Style::default().fg(status_color(&msg)),
+        ))
+        .alignment(Alignment::Center)
+        .render(layout[2], buf);
+        return;
+    }
+
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("{}", report.tables.len()),
+            Style::default().fg(TEXT_NORMAL),
+        ),
+        Span::styled(" tables", dim),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled("↑↓", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" select  ", dim),
+        Span::styled("r", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" refresh  ", dim),
+        Span::styled("b", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" back", dim),
+    ]))
+    .alignment(Alignment::Center)
+    .render(layout[2], buf);
+}
+
+/// Threshold colour for a "lower is better" figure, from green up to red
+/// at `red_cap`. Zero still reads as healthy rather than as "no data".
+fn health_color(value: f64, red_cap: u64) -> Color {
+    make_color_fn(red_cap, false)((value.ceil() as u64).max(1), red_cap)
+}
+
+/// Milliseconds with enough precision for sub-millisecond statements.
+fn format_ms(ms: f64) -> String {
+    match ms {
+        _ if ms < 10.0 => format!("{ms:.2}ms"),
+        _ if ms < 1000.0 => format!("{ms:.0}ms"),
+        _ => format_duration(ms / 1000.0),
+    }
+}
+
+/// Short elapsed time: `850ms`, `12s`, `4m05s`, `2h10m`, `3d04h`.
+fn format_duration(secs: f64) -> String {
+    let whole = secs.max(0.0) as u64;
+    match whole {
+        0 => format!("{}ms", (secs.max(0.0) * 1000.0) as u64),
+        1..60 => format!("{whole}s"),
+        60..3600 => format!("{}m{:02}s", whole / 60, whole % 60),
+        3600..86400 => format!("{}h{:02}m", whole / 3600, whole % 3600 / 60),
+        _ => format!("{}d{:02}h", whole / 86400, whole % 86400 / 3600),
+    }
+}
+
+fn erd_style(kind: Option<CellKind>) -> Style {
+    let color = match kind {
+        Some(CellKind::Title) => return Style::default().fg(TEXT_NORMAL).bold(),
+        Some(CellKind::PrimaryKey) => PK_COLOR,
+        Some(CellKind::ForeignKey) => Color::Cyan,
+        Some(CellKind::Column) => TEXT_NORMAL,
+        Some(CellKind::Border) => ICON_GRAY,
+        Some(CellKind::Edge) => BORDER_FOCUSED,
+        Some(CellKind::Empty) | None => TEXT_DIM,
+    };
+    Style::default().fg(color)
+}
+
+fn render_query_results(qr: &QueryResultState, app: &App, area: Rect, buf: &mut Buffer) {
+    let title = if qr.error.is_some() {
+        " Query Error "
+    } else {
+        " Query Results "
+    };
+    let block = pane_block(title, app.focused_pane == FocusedPane::Results);
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let layout = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).split(inner);
+
+    // Feed actual visible height back so key handlers use the real row count.
+    app.results_area_height
+        .set(layout[0].height.saturating_sub(1));
+
+    if let Some(error) = &qr.error {
+        Paragraph::new(error.clone())
+            .style(Style::default().fg(TEXT_ERROR))
+            .wrap(Wrap { trim: false })
+            .render(layout[0], buf);
+    } else if qr.rows.is_empty() {
+        if qr.columns.is_empty() {
+            render_centered_message(
+                layout[0],
+                buf,
+                "✓ ",
+                "Query executed successfully",
+                TEXT_SUCCESS,
+            );
+        } else {
+            render_centered_message(layout[0], buf, "", "<no rows returned>", TEXT_DIM);
+        }
+    } else if qr.is_explain {
+        let lines: Vec<Line> = qr
+            .rows
+            .iter()
+            .map(|row| {
+                Line::from(Span::styled(
+                    row.first().map(|s| s.as_str()).unwrap_or(""),
+                    Style::default().fg(TEXT_NORMAL),
+                ))
+            })
+            .collect();
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .render(layout[0], buf);
+    } else {
+        render_data_table(
+            &qr.columns,
+            &qr.rows,
+            qr.selected_row,
+            qr.scroll_offset,
+            qr.col_offset,
+            layout[0],
+            buf,
+        );
+    }
+
+    if let Some(msg) = app.export_status_text() {
+        let color = status_color(&msg);
+        Paragraph::new(Span::styled(msg, Style::default().fg(color)))
+            .alignment(Alignment::Center)
+            .render(layout[1], buf);
+    } else {
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} rows", qr.row_count),
+                Style::default().fg(TEXT_NORMAL),
+            ),
+            Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+            Span::styled(
+                format!("{}ms", qr.duration_ms),
+                Style::default().fg(TEXT_SUCCESS),
+            ),
+            Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+            Span::styled("c", Style::default().fg(TEXT_NORMAL)),
+            Span::styled(" clear  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("y", Style::default().fg(TEXT_NORMAL)),
+            Span::styled(" copy  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("x", Style::default().fg(TEXT_NORMAL)),
+            Span::styled(" export", Style::default().fg(TEXT_DIM)),
+        ]))
+        .alignment(Alignment::Center)
+        .render(layout[1], buf);
+    }
+}
+
+/// Footer colour for a status message: ✓ success, ⟳ in progress, else error.
+fn status_color(msg: &str) -> Color {
+    if msg.starts_with('✓') {
+        TEXT_SUCCESS
+    } else if msg.starts_with('⟳') {
+        BORDER_FOCUSED
+    } else {
+        TEXT_ERROR
+    }
+}
+
+fn render_centered_message(area: Rect, buf: &mut Buffer, prefix: &str, msg: &str, color: Color) {
+    let centered = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+    Paragraph::new(Line::from(vec![
+        Span::styled(prefix, Style::default().fg(color)),
+        Span::styled(msg, Style::default().fg(color)),
+    ]))
+    .alignment(Alignment::Center)
+    .render(centered[1], buf);
+}
+
+fn render_data_table(
+    columns: &[String],
+    rows: &[Vec<String>],
+    selected_row: usize,
+    scroll_offset: usize,
+    col_offset: usize,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    if columns.is_empty() {
+        return;
+    }
+
+    let mut col_widths: Vec<usize> = columns.iter().map(|c| c.len()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if i < col_widths.len() {
+                col_widths[i] = col_widths[i].max(cell.len().min(30));
+            }
+        }
+    }
+
+    // Apply column offset — skip the first col_offset columns
+    let col_offset = col_offset.min(columns.len().saturating_sub(1));
+    let vis_columns = &columns[col_offset..];
+    let vis_widths = &col_widths[col_offset..];
+
+    let constraints: Vec<Constraint> = vis_widths
+        .iter()
+        .map(|&w| Constraint::Length((w + 2) as u16))
+        .collect();
+    let header = Row::new(
+        vis_columns
+            .iter()
+            .map(|col| Cell::from(col.clone()).style(Style::default().fg(TEXT_NORMAL).bold())),
+    )
+    .height(1);
+
+    let visible_rows = area.height.saturating_sub(1) as usize;
+    let end_idx = (scroll_offset + visible_rows).min(rows.len());
+
+    let data_rows: Vec<Row> = rows[scroll_offset..end_idx]
+        .iter()
+        .enumerate()
+        .map(|(visible_idx, row)| {
+            let is_selected = scroll_offset + visible_idx == selected_row;
+            let row_style = if is_selected {
+                Style::default().bg(SELECTED_BG).fg(SELECTED_FG)
+            } else {
+                Style::default()
+            };
+
+            let cells: Vec<Cell> = row
+                .iter()
+                .skip(col_offset)
+                .enumerate()
+                .map(|(i, cell)| {
+                    let display = if cell.len() > 30 {
                         format!("{}…", &cell[..29])
                     } else {
                         cell.clone()
                     };
-                    let style = if is_selected {
+                    // The leftmost visible cell of the selected row is the
+                    // "current cell" that yank commands act on.
+                    let style = if is_selected && i == 0 {
+                        Style::default()
+                            .fg(SELECTED_FG)
+                            .bg(SELECTED_BG)
+                            .add_modifier(Modifier::REVERSED)
+                    } else if is_selected {
                         Style::default().fg(SELECTED_FG).bg(SELECTED_BG)
                     } else if cell == "NULL" {
                         Style::default().fg(TEXT_DIM).italic()
@@ -920,16 +2479,13 @@ fn render_data_table(
 
 fn render_table_footer(
     state: &TableViewState,
+    has_history: bool,
     export_msg: Option<&str>,
     area: Rect,
     buf: &mut Buffer,
 ) {
     if let Some(msg) = export_msg {
-        let color = if msg.starts_with('✓') {
-            TEXT_SUCCESS
-        } else {
-            TEXT_ERROR
-        };
+        let color = status_color(msg);
         Paragraph::new(Span::styled(msg, Style::default().fg(color)))
             .alignment(Alignment::Center)
             .render(area, buf);
@@ -959,8 +2515,24 @@ fn render_table_footer(
         Span::styled(" row  ", Style::default().fg(TEXT_DIM)),
         Span::styled("⇧←→", Style::default().fg(TEXT_NORMAL)),
         Span::styled(" cols  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("↵", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" detail  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("y", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" copy  ", Style::default().fg(TEXT_DIM)),
         Span::styled("x", Style::default().fg(TEXT_NORMAL)),
-        Span::styled(" export", Style::default().fg(TEXT_DIM)),
+        Span::styled(" export  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("f", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" follow  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("R", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" refs", Style::default().fg(TEXT_DIM)),
+        Span::styled(
+            if has_history { "  b" } else { "" },
+            Style::default().fg(TEXT_NORMAL),
+        ),
+        Span::styled(
+            if has_history { " back" } else { "" },
+            Style::default().fg(TEXT_DIM),
+        ),
     ]))
     .alignment(Alignment::Center)
     .render(area, buf);
@@ -1271,7 +2843,11 @@ fn render_connection_list(cm: &crate::app::ConnectionManagerState, area: Rect, b
 
                 let line = Line::from(vec![
                     Span::styled(
-                        if is_selected { "▸ " } else { "  " },
+                        match (is_selected, cm.diff_source == Some(i)) {
+                            (_, true) => "◆ ",
+                            (true, false) => "▸ ",
+                            (false, false) => "  ",
+                        },
                         Style::default().fg(BORDER_FOCUSED),
                     ),
                     Span::styled(
@@ -1311,7 +2887,7 @@ fn render_connection_list(cm: &crate::app::ConnectionManagerState, area: Rect, b
     }
 
     // Footer with keybindings
-    Paragraph::new(vec![Line::from(vec![
+    let mut lines = vec![Line::from(vec![
         Span::styled("↑↓", Style::default().fg(TEXT_NORMAL)),
         Span::styled(" navigate  ", Style::default().fg(TEXT_DIM)),
         Span::styled("←→", Style::default().fg(TEXT_NORMAL)),
@@ -1326,9 +2902,23 @@ fn render_connection_list(cm: &crate::app::ConnectionManagerState, area: Rect, b
         Span::styled("/", Style::default().fg(TEXT_DIM)),
         Span::styled("Esc", Style::default().fg(TEXT_NORMAL)),
         Span::styled(" close", Style::default().fg(TEXT_DIM)),
-    ])])
-    .alignment(Alignment::Center)
-    .render(footer_area, buf);
+    ])];
+    lines.push(Line::from(vec![
+        Span::styled("s", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(" mark diff source  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("D", Style::default().fg(TEXT_NORMAL)),
+        Span::styled(
+            if cm.diff_source.is_some() {
+                " diff source → selected"
+            } else {
+                " diff (mark a source first)"
+            },
+            Style::default().fg(TEXT_DIM),
+        ),
+    ]));
+    Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .render(footer_area, buf);
 }
 
 fn render_add_connection_input(
@@ -1412,17 +3002,172 @@ fn render_add_connection_input(
         .render(layout[5], buf);
 }
 
-fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max_len - 1])
-    }
+fn truncate_str(s: &str, max_len: usize) -> String {
+    if s.len() <= max_len {
+        s.to_string()
+    } else {
+        format!("{}…", &s[..max_len - 1])
+    }
+}
+
+fn render_export_modal(app: &App, area: Rect, buf: &mut Buffer) {
+    let modal_width = 52u16.min(area.width.saturating_sub(4));
+    let modal_height = 16u16.min(area.height.saturating_sub(4));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    Clear.render(modal_area, buf);
+
+    let block = Block::bordered()
+        .title(" Export ")
+        .title_style(Style::default().fg(BORDER_FOCUSED).bold())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_FOCUSED))
+        .style(Style::default().bg(BG));
+
+    let inner = block.inner(modal_area);
+    block.render(modal_area, buf);
+
+    let layout = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(3),
+        Constraint::Min(1),
+    ])
+    .split(inner);
+
+    let state = &app.export_modal;
+    Paragraph::new(Span::styled("Path:", Style::default().fg(TEXT_DIM))).render(layout[0], buf);
+
+    let path_box = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(if state.editing_path {
+            Style::default().fg(BORDER_FOCUSED)
+        } else {
+            Style::default().fg(TEXT_DIM)
+        });
+    let path_inner = path_box.inner(layout[1]);
+    path_box.render(layout[1], buf);
+
+    let path_display = if state.editing_path {
+        format!("{}▌", state.path_template)
+    } else {
+        state.path_template.clone()
+    };
+    // Keep the end of long paths (and the cursor) visible
+    let overflow = path_display
+        .chars()
+        .count()
+        .saturating_sub(path_inner.width as usize);
+    let path_display: String = path_display.chars().skip(overflow).collect();
+    Paragraph::new(Span::styled(path_display, Style::default().fg(TEXT_NORMAL)))
+        .render(path_inner, buf);
+
+    let footer = if state.editing_path {
+        vec![
+            Line::from(Span::styled(
+                "{table} {timestamp} {date} {ext}",
+                Style::default().fg(TEXT_DIM),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled("/", Style::default().fg(TEXT_DIM)),
+                Span::styled("Esc", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" done", Style::default().fg(TEXT_DIM)),
+            ]),
+        ]
+    } else {
+        vec![
+            Line::from(vec![
+                Span::styled("c", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" CSV   ", Style::default().fg(TEXT_DIM)),
+                Span::styled("t", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" TSV", Style::default().fg(TEXT_DIM)),
+            ]),
+            Line::from(vec![
+                Span::styled("j", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" JSON  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("l", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" JSON Lines", Style::default().fg(TEXT_DIM)),
+            ]),
+            Line::from(vec![
+                Span::styled("m", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" Markdown  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("s", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" SQL INSERT", Style::default().fg(TEXT_DIM)),
+            ]),
+            Line::from(vec![
+                Span::styled("x", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" Excel  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("h", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" HTML", Style::default().fg(TEXT_DIM)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("f", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" full export: ", Style::default().fg(TEXT_DIM)),
+                if state.full {
+                    Span::styled("all rows from server", Style::default().fg(TEXT_SUCCESS))
+                } else {
+                    Span::styled("loaded rows only", Style::default().fg(TEXT_NORMAL))
+                },
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("p", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" edit path  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("Esc", Style::default().fg(TEXT_NORMAL).bold()),
+                Span::styled(" cancel", Style::default().fg(TEXT_DIM)),
+            ]),
+        ]
+    };
+
+    Paragraph::new(footer)
+        .alignment(Alignment::Center)
+        .render(layout[2], buf);
+}
+
+fn render_editor_replace_modal(area: Rect, buf: &mut Buffer) {
+    let modal_width = 40u16.min(area.width.saturating_sub(4));
+    let modal_height = 7u16.min(area.height.saturating_sub(4));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    Clear.render(modal_area, buf);
+
+    let block = Block::bordered()
+        .title(" SQL editor ")
+        .title_style(Style::default().fg(BORDER_FOCUSED).bold())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_FOCUSED))
+        .style(Style::default().bg(BG));
+
+    let inner = block.inner(modal_area);
+    block.render(modal_area, buf);
+
+    Paragraph::new(vec![
+        Line::from(Span::styled(
+            "The editor already has a query.",
+            Style::default().fg(TEXT_DIM),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("r", Style::default().fg(TEXT_NORMAL).bold()),
+            Span::styled(" replace  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("a", Style::default().fg(TEXT_NORMAL).bold()),
+            Span::styled(" append  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("Esc", Style::default().fg(TEXT_NORMAL).bold()),
+            Span::styled(" cancel", Style::default().fg(TEXT_DIM)),
+        ]),
+    ])
+    .render(inner, buf);
 }
 
-fn render_export_modal(area: Rect, buf: &mut Buffer) {
+fn render_copy_modal(area: Rect, buf: &mut Buffer) {
     let modal_width = 38u16.min(area.width.saturating_sub(4));
-    let modal_height = 9u16.min(area.height.saturating_sub(4));
+    let modal_height = 10u16.min(area.height.saturating_sub(4));
     let modal_x = (area.width.saturating_sub(modal_width)) / 2;
     let modal_y = (area.height.saturating_sub(modal_height)) / 2;
     let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
@@ -1430,7 +3175,7 @@ fn render_export_modal(area: Rect, buf: &mut Buffer) {
     Clear.render(modal_area, buf);
 
     let block = Block::bordered()
-        .title(" Export ")
+        .title(" Copy ")
         .title_style(Style::default().fg(BORDER_FOCUSED).bold())
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(BORDER_FOCUSED))
@@ -1441,21 +3186,25 @@ fn render_export_modal(area: Rect, buf: &mut Buffer) {
 
     Paragraph::new(vec![
         Line::from(Span::styled(
-            "Choose a format:",
+            "Copy to clipboard:",
             Style::default().fg(TEXT_DIM),
         )),
         Line::from(""),
         Line::from(vec![
             Span::styled("c", Style::default().fg(TEXT_NORMAL).bold()),
-            Span::styled(" CSV   ", Style::default().fg(TEXT_DIM)),
-            Span::styled("t", Style::default().fg(TEXT_NORMAL).bold()),
-            Span::styled(" TSV", Style::default().fg(TEXT_DIM)),
+            Span::styled(" cell  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("o", Style::default().fg(TEXT_NORMAL).bold()),
+            Span::styled(" column", Style::default().fg(TEXT_DIM)),
         ]),
         Line::from(vec![
+            Span::styled("t", Style::default().fg(TEXT_NORMAL).bold()),
+            Span::styled(" row TSV  ", Style::default().fg(TEXT_DIM)),
             Span::styled("j", Style::default().fg(TEXT_NORMAL).bold()),
-            Span::styled(" JSON  ", Style::default().fg(TEXT_DIM)),
-            Span::styled("m", Style::default().fg(TEXT_NORMAL).bold()),
-            Span::styled(" Markdown", Style::default().fg(TEXT_DIM)),
+            Span::styled(" row JSON", Style::default().fg(TEXT_DIM)),
+        ]),
+        Line::from(vec![
+            Span::styled("i", Style::default().fg(TEXT_NORMAL).bold()),
+            Span::styled(" row INSERT", Style::default().fg(TEXT_DIM)),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -1466,3 +3215,476 @@ fn render_export_modal(area: Rect, buf: &mut Buffer) {
     .alignment(Alignment::Center)
     .render(inner, buf);
 }
+
+fn render_finder(finder: &FinderState, area: Rect, buf: &mut Buffer) {
+    let modal_width = 72u16.min(area.width.saturating_sub(4));
+    let modal_height = 22u16.min(area.height.saturating_sub(4));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 3;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    Clear.render(modal_area, buf);
+
+    let block = Block::bordered()
+        .title(" Find ")
+        .title_style(Style::default().fg(BORDER_FOCUSED).bold())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_FOCUSED))
+        .style(Style::default().bg(BG));
+
+    let inner = block.inner(modal_area);
+    block.render(modal_area, buf);
+
+    let layout = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .split(inner);
+
+    Paragraph::new(Line::from(vec![
+        Span::styled("/ ", Style::default().fg(BORDER_FOCUSED).bold()),
+        Span::styled(finder.query.clone(), Style::default().fg(TEXT_NORMAL)),
+        Span::styled("█", Style::default().fg(BORDER_FOCUSED)),
+    ]))
+    .render(layout[0], buf);
+
+    let list_height = layout[2].height as usize;
+    let scroll = finder
+        .selected
+        .saturating_sub(list_height.saturating_sub(1));
+    let lines: Vec<Line> = finder
+        .matches
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(list_height)
+        .map(|(idx, m)| {
+            let candidate = &finder.candidates[m.candidate];
+            let icon = match candidate.kind {
+                CandidateKind::Schema => "󰉋 ",
+                CandidateKind::Table => "󰓫 ",
+                CandidateKind::View => "󰈈 ",
+                CandidateKind::Column => "• ",
+            };
+            let mut spans = vec![Span::styled(icon, Style::default().fg(ICON_GRAY))];
+            for (i, ch) in candidate.label.chars().enumerate() {
+                let style = if m.positions.contains(&i) {
+                    Style::default().fg(MATCH_COLOR).bold()
+                } else if i < candidate.name_start {
+                    Style::default().fg(TEXT_DIM)
+                } else {
+                    Style::default().fg(TEXT_NORMAL)
+                };
+                spans.push(Span::styled(ch.to_string(), style));
+            }
+            let line = Line::from(spans);
+            if idx == finder.selected {
+                line.style(Style::default().bg(CURSOR_LINE_BG))
+            } else {
+                line
+            }
+        })
+        .collect();
+    if lines.is_empty() {
+        Paragraph::new(Span::styled("No matches", Style::default().fg(TEXT_DIM)))
+            .alignment(Alignment::Center)
+            .render(layout[2], buf);
+    } else {
+        Paragraph::new(lines).render(layout[2], buf);
+    }
+
+    let mode = if finder.filter_mode {
+        "filter tree"
+    } else {
+        "jump"
+    };
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("{} matches", finder.matches.len()),
+            Style::default().fg(TEXT_NORMAL),
+        ),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled("↑↓", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(" select  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("Enter", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(" go  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("Tab", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(format!(" mode: {mode}  "), Style::default().fg(TEXT_DIM)),
+        Span::styled("Esc", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(" cancel", Style::default().fg(TEXT_DIM)),
+    ]))
+    .alignment(Alignment::Center)
+    .render(layout[3], buf);
+}
+
+fn render_references_modal(state: &ReferencesModalState, area: Rect, buf: &mut Buffer) {
+    let modal_width = 64u16.min(area.width.saturating_sub(4));
+    let modal_height = (state.entries.len() as u16 + 6).min(area.height.saturating_sub(4));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    Clear.render(modal_area, buf);
+
+    let block = Block::bordered()
+        .title(" References ")
+        .title_style(Style::default().fg(BORDER_FOCUSED).bold())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_FOCUSED))
+        .style(Style::default().bg(BG));
+
+    let inner = block.inner(modal_area);
+    block.render(modal_area, buf);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("Rows referencing {}:", state.title),
+            Style::default().fg(TEXT_DIM),
+        )),
+        Line::from(""),
+    ];
+    for (idx, entry) in state.entries.iter().enumerate() {
+        let (count, count_color) = match &entry.count {
+            None => ("…".to_string(), TEXT_DIM),
+            Some(Ok(n)) => (n.to_string(), TEXT_NORMAL),
+            Some(Err(_)) => ("✗".to_string(), TEXT_ERROR),
+        };
+        let selected = idx == state.selected;
+        let marker = if selected { "▸ " } else { "  " };
+        let name_style = if selected {
+            Style::default().fg(BORDER_FOCUSED).bold()
+        } else {
+            Style::default().fg(TEXT_NORMAL)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, name_style),
+            Span::styled(format!("{}.{}", entry.schema, entry.table), name_style),
+            Span::styled(
+                format!(" ({}) ", entry.filter.columns.join(", ")),
+                Style::default().fg(TEXT_DIM),
+            ),
+            Span::styled(count, Style::default().fg(count_color)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("↑↓", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(" select  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("Enter", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(" open  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("Esc", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(" cancel", Style::default().fg(TEXT_DIM)),
+    ]));
+
+    Paragraph::new(lines).render(inner, buf);
+}
+
+fn render_row_detail(state: &RowDetailState, area: Rect, buf: &mut Buffer) {
+    let modal_width = 90u16.min(area.width.saturating_sub(4));
+    let modal_height = area.height.saturating_sub(6).max(8).min(area.height);
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    Clear.render(modal_area, buf);
+
+    let block = Block::bordered()
+        .title(format!(" {} ", state.title))
+        .title_style(Style::default().fg(BORDER_FOCUSED).bold())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_FOCUSED))
+        .style(Style::default().bg(BG));
+
+    let inner = block.inner(modal_area);
+    block.render(modal_area, buf);
+
+    let layout = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner);
+
+    let mut lines = Vec::new();
+    for field in state.fields.iter().skip(state.scroll) {
+        let name_style = if field.is_primary_key {
+            Style::default().fg(PK_COLOR).bold()
+        } else {
+            Style::default().fg(TEXT_NORMAL).bold()
+        };
+        let mut header = vec![Span::styled(field.name.clone(), name_style)];
+        if let Some(data_type) = &field.data_type {
+            header.push(Span::styled(
+                format!(" {data_type}"),
+                Style::default().fg(TEXT_DIM),
+            ));
+        }
+        if let Some(comment) = &field.comment {
+            header.push(Span::styled(
+                format!(
+                    "  -- {}",
+                    comment.split_whitespace().collect::<Vec<_>>().join(" ")
+                ),
+                Style::default().fg(TEXT_DIM).italic(),
+            ));
+        }
+        lines.push(Line::from(header));
+
+        if field.value == "NULL" {
+            lines.push(Line::from(Span::styled(
+                "  NULL",
+                Style::default().fg(TEXT_DIM).italic(),
+            )));
+        } else {
+            for value_line in field.value.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("  {value_line}"),
+                    Style::default().fg(TEXT_NORMAL),
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+    }
+
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .render(layout[0], buf);
+
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("{}/{}  ", state.scroll + 1, state.fields.len()),
+            Style::default().fg(TEXT_DIM),
+        ),
+        Span::styled("↑↓", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(" scroll  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("Esc", Style::default().fg(TEXT_NORMAL).bold()),
+        Span::styled(" close", Style::default().fg(TEXT_DIM)),
+    ]))
+    .alignment(Alignment::Center)
+    .render(layout[1], buf);
+}
+
+fn render_import_modal(state: &ImportModalState, area: Rect, buf: &mut Buffer) {
+    let modal_width = (area.width * 70 / 100)
+        .max(50)
+        .min(area.width.saturating_sub(4));
+    let modal_height = (area.height * 70 / 100)
+        .max(14)
+        .min(area.height.saturating_sub(4));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    Clear.render(modal_area, buf);
+
+    let title = format!(" Import into {}.{} ", state.schema, state.table);
+    let block = Block::bordered()
+        .title(title)
+        .title_style(Style::default().fg(BORDER_FOCUSED).bold())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_FOCUSED))
+        .style(Style::default().bg(BG));
+
+    let inner = block.inner(modal_area);
+    block.render(modal_area, buf);
+
+    let layout = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner);
+
+    let mut lines: Vec<Line> = Vec::new();
+    match state.stage {
+        ImportStage::Path => {
+            lines.push(Line::from(Span::styled(
+                "CSV/TSV file path (.tsv is tab-separated):",
+                Style::default().fg(TEXT_DIM),
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("{}▌", state.path_input),
+                Style::default().fg(TEXT_NORMAL),
+            )));
+        }
+        ImportStage::Preview => {
+            if let Some(preview) = &state.preview {
+                lines.extend(import_preview_lines(preview, state.scroll));
+            }
+        }
+        ImportStage::Running => {
+            let total = state.preview.as_ref().map_or(0, |p| p.rows.len());
+            lines.push(Line::from(vec![
+                Span::styled("⟳ Importing… ", Style::default().fg(BORDER_FOCUSED).bold()),
+                Span::styled(
+                    format!("{} / {} rows", state.inserted, total),
+                    Style::default().fg(TEXT_NORMAL),
+                ),
+            ]));
+        }
+        ImportStage::Done => {
+            if let Some(report) = &state.report {
+                lines.push(Line::from(vec![
+                    Span::styled("✓ ", Style::default().fg(TEXT_SUCCESS)),
+                    Span::styled(
+                        format!("Inserted {} rows", report.inserted),
+                        Style::default().fg(TEXT_SUCCESS).bold(),
+                    ),
+                    Span::styled(
+                        format!(" │ {} rejected", report.rejected.len()),
+                        Style::default().fg(if report.rejected.is_empty() {
+                            TEXT_DIM
+                        } else {
+                            TEXT_ERROR
+                        }),
+                    ),
+                ]));
+                lines.push(Line::from(""));
+                lines.extend(rejected_lines(&report.rejected, state.scroll));
+            }
+        }
+    }
+
+    if let Some(error) = &state.error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("✗ {error}"),
+            Style::default().fg(TEXT_ERROR),
+        )));
+    }
+
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .render(layout[0], buf);
+
+    let hints: &[(&str, &str)] = match state.stage {
+        ImportStage::Path => &[("Enter", " load  "), ("Esc", " cancel")],
+        ImportStage::Preview => &[
+            ("Enter", " import  "),
+            ("↑↓", " scroll  "),
+            ("Backspace", " change file  "),
+            ("Esc", " cancel"),
+        ],
+        ImportStage::Running => &[],
+        ImportStage::Done => &[("↑↓", " scroll  "), ("Esc", " close")],
+    };
+    let spans: Vec<Span> = hints
+        .iter()
+        .flat_map(|(key, label)| {
+            [
+                Span::styled(*key, Style::default().fg(TEXT_NORMAL)),
+                Span::styled(*label, Style::default().fg(TEXT_DIM)),
+            ]
+        })
+        .collect();
+    Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Center)
+        .render(layout[1], buf);
+}
+
+fn import_preview_lines(
+    preview: &crate::import::ImportPreview,
+    scroll: usize,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            preview.path.display().to_string(),
+            Style::default().fg(TEXT_NORMAL).bold(),
+        ),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled(
+            format!("{} valid", preview.rows.len()),
+            Style::default().fg(TEXT_SUCCESS),
+        ),
+        Span::styled(" │ ", Style::default().fg(SEPARATOR)),
+        Span::styled(
+            format!("{} rejected", preview.rejected.len()),
+            Style::default().fg(if preview.rejected.is_empty() {
+                TEXT_DIM
+            } else {
+                TEXT_ERROR
+            }),
+        ),
+    ])];
+
+    let mut mapping = vec![Span::styled("Columns: ", Style::default().fg(TEXT_DIM))];
+    for (i, (header, column)) in preview.mapping.iter().enumerate() {
+        if i > 0 {
+            mapping.push(Span::styled(", ", Style::default().fg(SEPARATOR)));
+        }
+        match column {
+            Some(col) if col == header => {
+                mapping.push(Span::styled(col.clone(), Style::default().fg(TEXT_NORMAL)));
+            }
+            Some(col) => mapping.push(Span::styled(
+                format!("{header} → {col}"),
+                Style::default().fg(TEXT_NORMAL),
+            )),
+            None => mapping.push(Span::styled(
+                format!("{header} (ignored)"),
+                Style::default().fg(TEXT_DIM).italic(),
+            )),
+        }
+    }
+    lines.push(Line::from(mapping));
+
+    if !preview.missing_required.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "⚠ Not in file (need a default): {}",
+                preview.missing_required.join(", ")
+            ),
+            Style::default().fg(BORDER_FOCUSED),
+        )));
+    }
+
+    lines.push(Line::from(""));
+    let mut header = vec![Span::styled(
+        format!("{:>6} ", "line"),
+        Style::default().fg(TEXT_DIM),
+    )];
+    for col in &preview.columns {
+        header.push(Span::styled(
+            format!("{:<16} ", truncate_str(col, 16)),
+            Style::default().fg(TEXT_NORMAL).bold(),
+        ));
+    }
+    lines.push(Line::from(header));
+
+    for row in &preview.preview {
+        let mut spans = vec![Span::styled(
+            format!("{:>6} ", row.line),
+            Style::default().fg(TEXT_DIM),
+        )];
+        for (value, error) in &row.cells {
+            let style = if error.is_some() {
+                Style::default().fg(TEXT_ERROR).bold()
+            } else if value.is_empty() || value == "NULL" {
+                Style::default().fg(TEXT_DIM).italic()
+            } else {
+                Style::default().fg(TEXT_NORMAL)
+            };
+            spans.push(Span::styled(
+                format!("{:<16} ", truncate_str(value, 16)),
+                style,
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if !preview.rejected.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(rejected_lines(&preview.rejected, scroll));
+    }
+    lines
+}
+
+fn rejected_lines(rejected: &[crate::import::RejectedRow], scroll: usize) -> Vec<Line<'static>> {
+    let scroll = scroll.min(rejected.len().saturating_sub(1));
+    rejected
+        .iter()
+        .skip(scroll)
+        .map(|r| {
+            Line::from(vec![
+                Span::styled(
+                    format!("line {:>6}: ", r.line),
+                    Style::default().fg(TEXT_DIM),
+                ),
+                Span::styled(r.reason.clone(), Style::default().fg(TEXT_ERROR)),
+            ])
+        })
+        .collect()
+}